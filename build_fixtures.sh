@@ -0,0 +1,20 @@
+#!/bin/bash
+
+# Rebuilds the WASM fixtures imported by the contract tests.
+
+set -e
+
+cd "$(dirname "$0")"
+
+cargo build --manifest-path contracts/voting-v2/Cargo.toml --target wasm32v1-none --release
+cp contracts/voting-v2/target/wasm32v1-none/release/voting_v2.wasm contracts/voting/testdata/
+cargo build -p voting --target wasm32v1-none --release
+
+# The network rejects contracts larger than this.
+MAX_WASM_SIZE=65536
+size=$(wc -c < target/wasm32v1-none/release/voting.wasm)
+if [ "$size" -gt "$MAX_WASM_SIZE" ]; then
+    echo "voting.wasm is $size bytes, over the $MAX_WASM_SIZE byte limit" >&2
+    exit 1
+fi
+cp target/wasm32v1-none/release/voting.wasm contracts/factory/testdata/
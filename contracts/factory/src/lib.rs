@@ -0,0 +1,134 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+const MAX_PAGE_SIZE: u32 = 50;
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    VotingWasmHash,
+    Election(u32),
+    ElectionCount,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElectionRecord {
+    pub election: Address,
+    pub admin: Address,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElectionInfo {
+    pub election: Address,
+    pub admin: Address,
+    pub created_at: u64,
+    pub finalized: bool,
+}
+
+#[contract]
+pub struct ElectionFactory;
+
+#[contractimpl]
+impl ElectionFactory {
+    pub fn initialize(env: Env, admin: Address, voting_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::VotingWasmHash, &voting_wasm_hash);
+        env.storage().instance().set(&DataKey::ElectionCount, &0u32);
+    }
+
+    pub fn set_voting_wasm_hash(env: Env, voting_wasm_hash: BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::VotingWasmHash, &voting_wasm_hash);
+    }
+
+    pub fn get_voting_wasm_hash(env: Env) -> BytesN<32> {
+        env.storage().instance()
+            .get(&DataKey::VotingWasmHash)
+            .expect("Contract not initialized")
+    }
+
+    /// Deploys and initializes a new election owned by `election_admin`; the
+    /// deployment is rolled back if initialization fails. The deploy salt is
+    /// derived from `election_admin` and `salt`, so nobody else can claim the
+    /// admin's election address first.
+    pub fn create_election(env: Env, election_admin: Address, salt: BytesN<32>) -> Address {
+        election_admin.require_auth();
+
+        let mut preimage = election_admin.clone().to_xdr(&env);
+        preimage.append(&Bytes::from(salt));
+        let wasm_hash = Self::get_voting_wasm_hash(env.clone());
+        let election = env.deployer()
+            .with_current_contract(env.crypto().sha256(&preimage).to_bytes())
+            .deploy_v2(wasm_hash, ());
+        env.invoke_contract::<()>(
+            &election,
+            &Symbol::new(&env, "initialize"),
+            vec![&env, election_admin.into_val(&env)],
+        );
+
+        let count = Self::get_election_count(env.clone());
+        env.storage().persistent().set(&DataKey::Election(count), &ElectionRecord {
+            election: election.clone(),
+            admin: election_admin.clone(),
+            created_at: env.ledger().timestamp(),
+        });
+        env.storage().instance().set(&DataKey::ElectionCount, &(count + 1));
+
+        env.events().publish((Symbol::new(&env, "election_created"), count), (election.clone(), election_admin));
+        election
+    }
+
+    pub fn get_election_count(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::ElectionCount)
+            .unwrap_or(0)
+    }
+
+    /// `finalized` is read from the election itself; an election that fails
+    /// to answer is reported as not finalized.
+    pub fn get_election(env: Env, index: u32) -> ElectionInfo {
+        let record: ElectionRecord = env.storage().persistent()
+            .get(&DataKey::Election(index))
+            .expect("Election not found");
+        let finalized = env.try_invoke_contract::<bool, soroban_sdk::InvokeError>(
+            &record.election,
+            &Symbol::new(&env, "is_finalized"),
+            Vec::new(&env),
+        );
+
+        ElectionInfo {
+            election: record.election,
+            admin: record.admin,
+            created_at: record.created_at,
+            finalized: matches!(finalized, Ok(Ok(true))),
+        }
+    }
+
+    /// Returns at most `MAX_PAGE_SIZE` elections starting at `offset`.
+    pub fn get_elections(env: Env, offset: u32, limit: u32) -> Vec<ElectionInfo> {
+        let end = Self::get_election_count(env.clone()).min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut elections = Vec::new(&env);
+        for index in offset..end {
+            elections.push_back(Self::get_election(env.clone(), index));
+        }
+        elections
+    }
+}
+
+mod test;
\ No newline at end of file
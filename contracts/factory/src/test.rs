@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{symbol_short, testutils::{Address as _, Ledger}, Address, BytesN, Env};
+
+// Release build of the voting contract. Rebuild the fixture with
+// `./build_fixtures.sh` whenever the voting contract changes.
+mod voting {
+    soroban_sdk::contractimport!(file = "testdata/voting.wasm");
+}
+
+// A contract without `is_finalized`, shared with the voting tests.
+mod voting_v2 {
+    soroban_sdk::contractimport!(file = "../voting/testdata/voting_v2.wasm");
+}
+
+fn setup_factory(env: &Env) -> (ElectionFactoryClient<'static>, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(ElectionFactory, ());
+    let client = ElectionFactoryClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    let wasm_hash = env.deployer().upload_contract_wasm(voting::WASM);
+    client.initialize(&admin, &wasm_hash);
+    (client, admin)
+}
+
+#[test]
+fn test_create_election() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+    let organiser = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 42);
+    let election = client.create_election(&organiser, &BytesN::from_array(&env, &[1u8; 32]));
+
+    let voting = voting::Client::new(&env, &election);
+    voting.add_party(&symbol_short!("PartyA"));
+    assert_eq!(voting.get_parties().len(), 1);
+
+    assert_eq!(client.get_election_count(), 1);
+    assert_eq!(client.get_election(&0), ElectionInfo {
+        election,
+        admin: organiser,
+        created_at: 42,
+        finalized: false,
+    });
+}
+
+#[test]
+fn test_get_elections_paginates() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+
+    let mut created = Vec::new(&env);
+    for i in 0..5u8 {
+        let organiser = Address::generate(&env);
+        created.push_back(client.create_election(&organiser, &BytesN::from_array(&env, &[i; 32])));
+    }
+
+    let page = client.get_elections(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().election, created.get(1).unwrap());
+    assert_eq!(page.get(1).unwrap().election, created.get(2).unwrap());
+    assert_eq!(client.get_elections(&4, &10).len(), 1);
+    assert_eq!(client.get_elections(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_registry_reports_finalized_elections() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+    let organiser = Address::generate(&env);
+
+    let election = client.create_election(&organiser, &BytesN::from_array(&env, &[1u8; 32]));
+    let voting = voting::Client::new(&env, &election);
+    voting.set_voting_deadline(&voting::Deadline::Timestamp(100), &soroban_sdk::String::from_str(&env, "initial"));
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    voting.finalize();
+
+    assert!(client.get_election(&0).finalized);
+}
+
+#[test]
+fn test_listing_survives_failing_election() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+
+    let broken = client.create_election(&Address::generate(&env), &BytesN::from_array(&env, &[1u8; 32]));
+    client.create_election(&Address::generate(&env), &BytesN::from_array(&env, &[2u8; 32]));
+
+    let legacy_hash = env.deployer().upload_contract_wasm(voting_v2::WASM);
+    voting::Client::new(&env, &broken).upgrade(&legacy_hash);
+
+    let elections = client.get_elections(&0, &u32::MAX);
+    assert_eq!(elections.len(), 2);
+    assert!(!elections.get(0).unwrap().finalized);
+}
+
+#[test]
+#[should_panic]
+fn test_create_election_rejects_reused_salt() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+    let organiser = Address::generate(&env);
+
+    client.create_election(&organiser, &salt);
+    client.create_election(&organiser, &salt);
+}
+
+#[test]
+fn test_salt_is_bound_to_election_admin() {
+    let env = Env::default();
+    let (client, _) = setup_factory(&env);
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+
+    let first = client.create_election(&Address::generate(&env), &salt);
+    let second = client.create_election(&Address::generate(&env), &salt);
+    assert_ne!(first, second);
+    assert_eq!(client.get_election_count(), 2);
+}
@@ -0,0 +1,37 @@
+#![no_std]
+
+//! Read-only view of an election for other contracts. Depend on this crate
+//! instead of `voting` and call through `VotingReaderClient`.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Symbol, Vec};
+
+/// Bumped whenever a function is added to or changed in `VotingReader`.
+pub const VOTING_READER_VERSION: u32 = 2;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FinalResult {
+    pub election: Address,
+    pub winner: Option<Symbol>,
+    pub winners: Vec<Symbol>,
+    pub results: Map<Symbol, u32>,
+    pub finalized_at: u64,
+}
+
+#[contractclient(name = "VotingReaderClient")]
+pub trait VotingReader {
+    fn reader_version(env: Env) -> u32;
+    fn get_result(env: Env, party_name: Symbol) -> u32;
+    fn is_finalized(env: Env) -> bool;
+    fn get_winner(env: Env) -> Option<Symbol>;
+    fn get_winners(env: Env) -> Vec<Symbol>;
+    /// True once the voter has cast their own ballot.
+    fn has_voted(env: Env, voter: Address) -> bool;
+}
+
+/// Implemented by contracts registered with `add_subscriber`; called once when
+/// the election is finalized.
+#[contractclient(name = "FinalizationSubscriberClient")]
+pub trait FinalizationSubscriber {
+    fn on_finalized(env: Env, result: FinalResult);
+}
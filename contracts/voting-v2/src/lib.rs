@@ -0,0 +1,28 @@
+#![no_std]
+
+// Minimal second version of the voting contract, used by the voting tests to
+// exercise `upgrade`. It is kept out of the workspace; rebuild the fixture
+// with `./build_fixtures.sh` from the repository root.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Env, Symbol};
+
+#[contracttype]
+pub enum DataKey {
+    Votes(Symbol),
+}
+
+#[contract]
+pub struct VotingContractV2;
+
+#[contractimpl]
+impl VotingContractV2 {
+    pub fn version() -> u32 {
+        2
+    }
+
+    pub fn get_vote_count(env: Env, party_name: Symbol) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::Votes(party_name))
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,534 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, Vec};
+
+use crate::{
+    delegation::DelegationKey, DataKey, Deadline, DeadlineChange, Invocation, PreviousDeadline, VoterStatus,
+    VotingContract, VotingContractArgs, VotingContractClient, ABSTAIN, NOTA, SCHEMA_VERSION,
+};
+
+const MAX_ADMIN_DELAY: u64 = 30 * 24 * 60 * 60;
+
+#[contracttype(export = false)]
+pub enum AdminKey {
+    Paused,
+    PausedAt,
+    Guardian,
+    AdminDelay,
+    ScheduledActions,
+    NextActionId,
+    Council,
+    CouncilActions,
+    NextCouncilActionId,
+    MigrationCursor,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    AddParty(Symbol),
+    AddVoter(Address),
+    SetVotingStart(Deadline),
+    SetVotingDeadline(Deadline, String),
+    SetAdminDelay(u64),
+    SetCouncil(Council),
+    Upgrade(BytesN<32>),
+    Migrate(Vec<Address>),
+    SetGuardian(Address),
+    SetEligibilityToken(Address, i128),
+    SetEligibilityOracle(Address),
+    EnableBondedVoting(Address, i128, bool),
+    EnableEncryptedBallots(Address, BytesN<96>, BytesN<192>),
+    SetVoterRoot(BytesN<32>),
+    SetDelegateOverride(bool),
+    SetCategory(Symbol),
+    SetNotaCompetes(bool),
+    SetSeats(u32, bool),
+    SetHideResults(bool),
+    SetMaxDeadlineExtension(u64),
+    AddSubscriber(Address),
+    RemoveSubscriber(Address),
+    SetProposal(Symbol, Vec<Invocation>),
+    SetExecutionDelay(u64),
+    SetProposalThreshold(u32, u32),
+    SetRewardPool(Address, i128, u64),
+    ReclaimRewards,
+    Cancel(u32),
+    Unpause,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Council {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CouncilAction {
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledAction {
+    pub action: AdminAction,
+    pub ready_at: u64,
+}
+
+#[contractimpl]
+impl VotingContract {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migrates storage written by the original (version 1) schema. Electorates of any
+    /// size are handled in batches: every registered voter must be passed
+    /// exactly once, in ascending address order across calls. Returns the
+    /// number of voters still to migrate; the schema version is only bumped
+    /// once every voter has been seen.
+    pub fn migrate(env: Env, voters: Vec<Address>) -> u32 {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::migrate_batch(env, voters)
+    }
+
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    pub fn set_guardian(env: Env, guardian: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_guardian(env, guardian);
+    }
+
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&AdminKey::Guardian)
+    }
+
+    pub fn pause(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        if caller != admin && Some(caller.clone()) != Self::get_guardian(env.clone()) {
+            panic!("Caller cannot pause the contract");
+        }
+
+        if Self::is_paused(env.clone()) {
+            panic!("Contract is paused");
+        }
+
+        env.storage().instance().set(&AdminKey::Paused, &true);
+        env.storage().instance().set(&AdminKey::PausedAt, &(env.ledger().timestamp(), env.ledger().sequence()));
+        env.events().publish((symbol_short!("paused"),), caller);
+    }
+
+    /// Voters cannot vote while the contract is paused, so unpausing pushes
+    /// the deadline back by the part of the pause that fell inside the voting
+    /// window.
+    pub fn unpause(env: Env) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+
+        if Self::get_council(env.clone()).is_some() {
+            panic!("Council approval required");
+        }
+
+        Self::resume(&env);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance()
+            .get(&AdminKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// While the delay is non-zero, every admin action, including upgrades and
+    /// the delay itself, can only be applied through `schedule`.
+    pub fn set_admin_delay(env: Env, delay: u64) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_admin_delay(env, delay);
+    }
+
+    pub fn get_admin_delay(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&AdminKey::AdminDelay)
+            .unwrap_or(0)
+    }
+
+    pub fn schedule(env: Env, action: AdminAction) -> u32 {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_council(env.clone()).is_some() {
+            panic!("Council approval required");
+        }
+
+        Self::enqueue_admin_action(&env, action)
+    }
+
+    pub fn cancel(env: Env, action_id: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_council(env.clone()).is_some() {
+            panic!("Council approval required");
+        }
+
+        Self::cancel_scheduled(&env, action_id);
+    }
+
+    pub fn execute_scheduled(env: Env, action_id: u32) {
+        Self::require_not_paused(&env);
+
+        let mut scheduled = Self::get_scheduled_actions(env.clone());
+        let entry = scheduled.get(action_id).expect("Action not found");
+
+        if env.ledger().timestamp() < entry.ready_at {
+            panic!("Action is still timelocked");
+        }
+
+        scheduled.remove(action_id);
+        env.storage().instance().set(&AdminKey::ScheduledActions, &scheduled);
+
+        Self::apply_admin_action(&env, entry.action);
+        env.events().publish((symbol_short!("applied"), action_id), ());
+    }
+
+    pub fn get_scheduled_actions(env: Env) -> Map<u32, ScheduledAction> {
+        env.storage().instance()
+            .get(&AdminKey::ScheduledActions)
+            .unwrap_or_else(|| Map::new(&env))
+    }
+
+    /// Once a council is set, admin actions need `threshold` member approvals
+    /// and the single admin key can no longer apply or schedule them.
+    pub fn set_council(env: Env, council: Council) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_council(env, council);
+    }
+
+    pub fn get_council(env: Env) -> Option<Council> {
+        env.storage().instance().get(&AdminKey::Council)
+    }
+
+    pub fn propose_action(env: Env, member: Address, action: AdminAction) -> u32 {
+        member.require_auth();
+        if action != AdminAction::Unpause {
+            Self::require_not_paused(&env);
+        }
+        let council = Self::require_council_member(&env, &member);
+
+        let action_id: u32 = env.storage().instance()
+            .get(&AdminKey::NextCouncilActionId)
+            .unwrap_or(0);
+        env.storage().instance().set(&AdminKey::NextCouncilActionId, &(action_id + 1));
+
+        let mut actions = Self::get_council_actions(env.clone());
+        actions.set(action_id, CouncilAction { action, approvals: Vec::new(&env) });
+        env.storage().instance().set(&AdminKey::CouncilActions, &actions);
+
+        Self::record_approval(&env, &council, member, action_id);
+        action_id
+    }
+
+    /// The approval that reaches the threshold applies the action, or queues
+    /// it behind the admin delay when one is set. Cancelling a scheduled action
+    /// and unpausing only undo earlier actions, so they always apply at once.
+    pub fn approve_action(env: Env, member: Address, action_id: u32) {
+        member.require_auth();
+        let pending = Self::get_council_actions(env.clone()).get(action_id).expect("Action not found");
+        if pending.action != AdminAction::Unpause {
+            Self::require_not_paused(&env);
+        }
+        let council = Self::require_council_member(&env, &member);
+        Self::record_approval(&env, &council, member, action_id);
+    }
+
+    pub fn get_council_actions(env: Env) -> Map<u32, CouncilAction> {
+        env.storage().instance()
+            .get(&AdminKey::CouncilActions)
+            .unwrap_or_else(|| Map::new(&env))
+    }
+
+    fn apply_admin_action(env: &Env, action: AdminAction) {
+        match action {
+            AdminAction::AddParty(party_name) => Self::insert_party(env.clone(), party_name),
+            AdminAction::AddVoter(voter) => Self::insert_voter(env.clone(), voter),
+            AdminAction::SetVotingStart(start) => Self::store_voting_start(env.clone(), start),
+            AdminAction::SetVotingDeadline(deadline, reason) => Self::store_voting_deadline(env.clone(), deadline, reason),
+            AdminAction::SetAdminDelay(delay) => Self::store_admin_delay(env.clone(), delay),
+            AdminAction::SetCouncil(council) => Self::store_council(env.clone(), council),
+            AdminAction::Upgrade(new_wasm_hash) => env.deployer().update_current_contract_wasm(new_wasm_hash),
+            AdminAction::Migrate(voters) => { Self::migrate_batch(env.clone(), voters); }
+            AdminAction::SetGuardian(guardian) => Self::store_guardian(env.clone(), guardian),
+            AdminAction::SetEligibilityToken(token, min_balance) => Self::store_eligibility_token(env.clone(), token, min_balance),
+            AdminAction::SetEligibilityOracle(oracle) => Self::store_eligibility_oracle(env.clone(), oracle),
+            AdminAction::EnableBondedVoting(token, amount, scale_weight) => Self::store_bond_config(env.clone(), token, amount, scale_weight),
+            AdminAction::EnableEncryptedBallots(trustee, public_key_g1, public_key_g2) => Self::store_encryption_key(env.clone(), trustee, public_key_g1, public_key_g2),
+            AdminAction::SetVoterRoot(root) => Self::store_voter_root(env.clone(), root),
+            AdminAction::SetDelegateOverride(enabled) => Self::store_delegate_override(env.clone(), enabled),
+            AdminAction::SetCategory(category) => Self::store_category(env.clone(), category),
+            AdminAction::SetNotaCompetes(enabled) => Self::store_nota_competes(env.clone(), enabled),
+            AdminAction::SetSeats(seats, block_voting) => Self::store_seats(env.clone(), seats, block_voting),
+            AdminAction::SetHideResults(enabled) => Self::store_hide_results(env.clone(), enabled),
+            AdminAction::SetMaxDeadlineExtension(max_extension) => Self::store_max_deadline_extension(env.clone(), max_extension),
+            AdminAction::AddSubscriber(subscriber) => Self::insert_subscriber(env.clone(), subscriber),
+            AdminAction::RemoveSubscriber(subscriber) => Self::delete_subscriber(env.clone(), subscriber),
+            AdminAction::SetProposal(party_name, invocations) => Self::store_proposal(env.clone(), party_name, invocations),
+            AdminAction::SetExecutionDelay(delay) => Self::store_execution_delay(env.clone(), delay),
+            AdminAction::SetProposalThreshold(quorum, majority_bps) => Self::store_proposal_threshold(env.clone(), quorum, majority_bps),
+            AdminAction::SetRewardPool(token, amount_per_vote, claim_window) => Self::store_reward_pool(env.clone(), token, amount_per_vote, claim_window),
+            AdminAction::ReclaimRewards => { Self::release_rewards(env.clone()); }
+            AdminAction::Cancel(action_id) => Self::cancel_scheduled(env, action_id),
+            AdminAction::Unpause => Self::resume(env),
+        }
+    }
+
+    fn resume(env: &Env) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        if !Self::is_paused(env.clone()) {
+            panic!("Contract is not paused");
+        }
+
+        env.storage().instance().set(&AdminKey::Paused, &false);
+        if let Some(paused_at) = env.storage().instance().get(&AdminKey::PausedAt) {
+            env.storage().instance().remove(&AdminKey::PausedAt);
+            Self::extend_for_pause(env, paused_at);
+        }
+        env.events().publish((symbol_short!("unpaused"),), admin);
+    }
+
+    fn cancel_scheduled(env: &Env, action_id: u32) {
+        let mut scheduled = Self::get_scheduled_actions(env.clone());
+        if scheduled.remove(action_id).is_none() {
+            panic!("Action not found");
+        }
+
+        env.storage().instance().set(&AdminKey::ScheduledActions, &scheduled);
+        env.events().publish((symbol_short!("cancelled"), action_id), ());
+    }
+
+    fn enqueue_admin_action(env: &Env, action: AdminAction) -> u32 {
+        let action_id: u32 = env.storage().instance()
+            .get(&AdminKey::NextActionId)
+            .unwrap_or(0);
+        let ready_at = env.ledger().timestamp() + Self::get_admin_delay(env.clone());
+
+        let mut scheduled = Self::get_scheduled_actions(env.clone());
+        scheduled.set(action_id, ScheduledAction { action: action.clone(), ready_at });
+        env.storage().instance().set(&AdminKey::ScheduledActions, &scheduled);
+        env.storage().instance().set(&AdminKey::NextActionId, &(action_id + 1));
+
+        env.events().publish((symbol_short!("scheduled"), action_id), (action, ready_at));
+        action_id
+    }
+
+    pub(crate) fn require_direct_admin(env: &Env) {
+        if Self::get_council(env.clone()).is_some() {
+            panic!("Council approval required");
+        }
+
+        if Self::get_admin_delay(env.clone()) > 0 {
+            panic!("Admin action must be scheduled");
+        }
+    }
+
+    fn record_approval(env: &Env, council: &Council, member: Address, action_id: u32) {
+        let mut actions = Self::get_council_actions(env.clone());
+        let mut pending = actions.get(action_id).expect("Action not found");
+
+        if pending.approvals.contains(&member) {
+            panic!("Action already approved");
+        }
+        pending.approvals.push_back(member);
+
+        if pending.approvals.len() < council.threshold {
+            actions.set(action_id, pending);
+            env.storage().instance().set(&AdminKey::CouncilActions, &actions);
+            return;
+        }
+
+        actions.remove(action_id);
+        env.storage().instance().set(&AdminKey::CouncilActions, &actions);
+
+        let undoes = matches!(pending.action, AdminAction::Cancel(_) | AdminAction::Unpause);
+        if Self::get_admin_delay(env.clone()) > 0 && !undoes {
+            Self::enqueue_admin_action(env, pending.action);
+        } else {
+            Self::apply_admin_action(env, pending.action);
+        }
+    }
+
+    fn require_council_member(env: &Env, member: &Address) -> Council {
+        let council = Self::get_council(env.clone()).expect("No council configured");
+        if !council.members.contains(member) {
+            panic!("Not a council member");
+        }
+        council
+    }
+
+    fn store_council(env: Env, council: Council) {
+        if council.threshold == 0 || council.threshold > council.members.len() {
+            panic!("Invalid council threshold");
+        }
+
+        for (i, member) in council.members.iter().enumerate() {
+            if council.members.first_index_of(&member) != Some(i as u32) {
+                panic!("Duplicate council member");
+            }
+        }
+
+        env.storage().instance().set(&AdminKey::Council, &council);
+        env.storage().instance().remove(&AdminKey::CouncilActions);
+    }
+
+    fn migrate_batch(env: Env, voters: Vec<Address>) -> u32 {
+        let version = Self::get_schema_version(env.clone());
+        if version >= SCHEMA_VERSION {
+            panic!("Contract already migrated");
+        }
+
+        let cursor: Option<(Address, u32)> = env.storage().instance().get(&AdminKey::MigrationCursor);
+        let (mut last, mut migrated) = match cursor {
+            Some((last, migrated)) => (Some(last), migrated),
+            None => (None, 0),
+        };
+
+        for voter in voters.iter() {
+            if last.as_ref().is_some_and(|last| voter <= *last) {
+                panic!("Voters must be in ascending order and not yet migrated");
+            }
+
+            match Self::get_voter_status(env.clone(), voter.clone()) {
+                VoterStatus::NotRegistered => panic!("Voter not registered"),
+                VoterStatus::Delegated(delegate) => {
+                    let mut delegators: Vec<Address> = env.storage().instance()
+                        .get(&DelegationKey::Delegators(delegate.clone()))
+                        .unwrap_or_else(|| Vec::new(&env));
+
+                    if !delegators.contains(&voter) {
+                        delegators.push_back(voter.clone());
+                        env.storage().instance().set(&DelegationKey::Delegators(delegate), &delegators);
+                    }
+                },
+                _ => {},
+            }
+            env.storage().instance().remove(&DataKey::DelegatedVotes(voter.clone()));
+
+            migrated += 1;
+            last = Some(voter);
+        }
+
+        let total_voters: u32 = env.storage().instance()
+            .get(&DataKey::TotalVoters)
+            .unwrap_or(0);
+        if migrated < total_voters {
+            if let Some(last) = last {
+                env.storage().instance().set(&AdminKey::MigrationCursor, &(last, migrated));
+            }
+            return total_voters - migrated;
+        }
+        env.storage().instance().remove(&AdminKey::MigrationCursor);
+
+        if let Some(deadline) = env.storage().instance().get::<DataKey, u64>(&DataKey::VotingDeadline) {
+            env.storage().instance().set(&DataKey::VotingDeadline, &Deadline::Timestamp(deadline));
+        }
+
+        let mut turnout = Self::tally(&env, ABSTAIN) + Self::tally(&env, NOTA);
+        for party in Self::get_parties(env.clone()).iter() {
+            turnout += Self::tally(&env, party);
+        }
+        env.storage().instance().set(&DataKey::Turnout, &turnout);
+
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        0
+    }
+
+    fn store_guardian(env: Env, guardian: Address) {
+        env.storage().instance().set(&AdminKey::Guardian, &guardian);
+    }
+
+    fn store_admin_delay(env: Env, delay: u64) {
+        if delay > MAX_ADMIN_DELAY {
+            panic!("Admin delay too long");
+        }
+
+        env.storage().instance().set(&AdminKey::AdminDelay, &delay);
+    }
+
+    fn extend_for_pause(env: &Env, paused_at: (u64, u32)) {
+        let deadline = match Self::get_voting_deadline(env.clone()) {
+            Some(deadline) => deadline,
+            None => return,
+        };
+
+        let paused_from = match deadline {
+            Deadline::Timestamp(_) => paused_at.0,
+            Deadline::Ledger(_) => paused_at.1 as u64,
+        };
+        if deadline.value() < paused_from {
+            return;
+        }
+
+        let lost_from = match Self::get_voting_start(env.clone()) {
+            Some(start) if start.value() > paused_from => start.value(),
+            _ => paused_from,
+        };
+        let now = deadline.current(env);
+        if now <= lost_from {
+            return;
+        }
+
+        let extended = deadline.shifted(now - lost_from);
+        if let Some(base) = env.storage().instance().get::<DataKey, Deadline>(&DataKey::BaseDeadline) {
+            env.storage().instance().set(&DataKey::BaseDeadline, &base.shifted(now - lost_from));
+        }
+        let mut history: Vec<DeadlineChange> = Self::get_deadline_history(env.clone());
+        history.push_back(DeadlineChange {
+            previous: PreviousDeadline::Set(deadline),
+            deadline: extended.clone(),
+            reason: String::from_str(env, "Extended for pause"),
+            changed_at: env.ledger().timestamp(),
+        });
+
+        env.storage().instance().set(&DataKey::DeadlineHistory, &history);
+        env.storage().instance().set(&DataKey::VotingDeadline, &extended);
+    }
+
+    pub(crate) fn require_not_paused(env: &Env) {
+        if Self::is_paused(env.clone()) {
+            panic!("Contract is paused");
+        }
+    }
+}
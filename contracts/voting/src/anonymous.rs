@@ -0,0 +1,173 @@
+use soroban_sdk::{contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::{DataKey, Deadline, Phase, VotingContract, VotingContractArgs, VotingContractClient, ABSTAIN, NOTA};
+
+/// Average ledger close time, used to turn a timestamp deadline into a TTL.
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+
+const COMMITMENT_DOMAIN: &[u8] = b"stellar-vote:anonymous:v1";
+
+#[contracttype(export = false)]
+pub enum AnonymousKey {
+    VoterRoot,
+    Nullifier(BytesN<32>),
+    BallotCommitment(BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MembershipProof {
+    pub secret: BytesN<32>,
+    pub index: u32,
+    pub path: Vec<BytesN<32>>,
+}
+
+impl Deadline {
+    fn ledgers_until(&self, env: &Env) -> u32 {
+        match self {
+            Deadline::Timestamp(timestamp) => {
+                let ledgers = timestamp.saturating_sub(env.ledger().timestamp()) / LEDGER_CLOSE_SECONDS + 1;
+                ledgers.min(u32::MAX as u64) as u32
+            },
+            Deadline::Ledger(sequence) => sequence.saturating_sub(env.ledger().sequence()).saturating_add(1),
+        }
+    }
+}
+
+#[contractimpl]
+impl VotingContract {
+    pub fn set_voter_root(env: Env, root: BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_voter_root(env, root);
+    }
+
+    pub fn get_voter_root(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&AnonymousKey::VoterRoot)
+    }
+
+    /// First step of an anonymous vote. `commitment` is
+    /// `sha256("stellar-vote:anonymous:v1" || xdr((election, party, secret)))`; it must
+    /// land in an earlier ledger than the matching `vote_anonymous`, so anyone
+    /// who learns the secret from the reveal cannot redirect the vote.
+    pub fn commit_anonymous(env: Env, commitment: BytesN<32>) {
+        Self::require_anonymous_ballots(&env);
+
+        // Commitments are only needed until the reveal, and anyone can submit
+        // them, so they stay out of instance storage. They are kept alive
+        // until the deadline so a delayed reveal still finds them.
+        let key = AnonymousKey::BallotCommitment(commitment);
+        if env.storage().temporary().has(&key) {
+            panic!("Commitment already recorded");
+        }
+        env.storage().temporary().set(&key, &env.ledger().sequence());
+
+        let max_ttl = env.storage().max_ttl();
+        let live_for = Self::get_voting_deadline(env.clone())
+            .map_or(max_ttl, |deadline| deadline.ledgers_until(&env).min(max_ttl));
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+    }
+
+    /// Reveals a vote committed with `commit_anonymous`. The nullifier is
+    /// `sha256("nullifier" || xdr(election) || secret)`.
+    pub fn vote_anonymous(env: Env, party_name: Symbol, nullifier: BytesN<32>, proof: MembershipProof) {
+        let root = Self::require_anonymous_ballots(&env);
+
+        if party_name != ABSTAIN && party_name != NOTA && !Self::get_parties(env.clone()).contains(&party_name) {
+            panic!("Party not found");
+        }
+
+        if Self::is_nullifier_used(env.clone(), nullifier.clone()) {
+            panic!("Nullifier already used");
+        }
+
+        if !Self::verify_membership(&env, &root, &nullifier, &proof) {
+            panic!("Invalid membership proof");
+        }
+
+        let mut preimage = Bytes::from_slice(&env, COMMITMENT_DOMAIN);
+        preimage.append(&(env.current_contract_address(), party_name.clone(), proof.secret).to_xdr(&env));
+        let key = AnonymousKey::BallotCommitment(env.crypto().sha256(&preimage).to_bytes());
+        match env.storage().temporary().get::<AnonymousKey, u32>(&key) {
+            Some(committed_at) if committed_at < env.ledger().sequence() => {},
+            Some(_) => panic!("Ballot must be committed in an earlier ledger"),
+            None => panic!("Ballot not committed"),
+        }
+        env.storage().temporary().remove(&key);
+
+        let current_votes = Self::tally(&env, party_name.clone());
+        env.storage().instance().set(&DataKey::Votes(party_name), &(current_votes + 1));
+        env.storage().instance().set(&DataKey::Turnout, &(Self::turnout(&env) + 1));
+        env.storage().instance().set(&AnonymousKey::Nullifier(nullifier), &true);
+        env.storage().instance().set(&DataKey::VotingStarted, &true);
+    }
+
+    pub fn is_nullifier_used(env: Env, nullifier: BytesN<32>) -> bool {
+        env.storage().instance().has(&AnonymousKey::Nullifier(nullifier))
+    }
+
+    pub(crate) fn anonymous_ballots(env: &Env) -> bool {
+        env.storage().instance().has(&AnonymousKey::VoterRoot)
+    }
+
+    pub(crate) fn store_voter_root(env: Env, root: BytesN<32>) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&AnonymousKey::VoterRoot, &root);
+    }
+
+    fn require_anonymous_ballots(env: &Env) -> BytesN<32> {
+        Self::require_not_paused(env);
+
+        let root = Self::get_voter_root(env.clone())
+            .expect("Anonymous voting is not enabled");
+        Self::require_unbonded(env);
+
+        if Self::encrypted_ballots(env) {
+            panic!("Election uses encrypted ballots");
+        }
+
+        match Self::get_phase(env.clone()) {
+            Phase::Pending => panic!("Voting has not started"),
+            Phase::Closed => panic!("Voting period has ended"),
+            Phase::Open => {},
+        }
+        root
+    }
+
+    /// Simple sha256 verifier: the leaf is `sha256(secret)` and the nullifier must be
+    /// `sha256("nullifier" || xdr(election) || secret)`. The secret is revealed to
+    /// the contract, so ballots are unlinkable only for observers who never saw the
+    /// commitments paired with identities; a zero-knowledge verifier can replace
+    /// this check without changing the entry point.
+    fn verify_membership(env: &Env, root: &BytesN<32>, nullifier: &BytesN<32>, proof: &MembershipProof) -> bool {
+        let mut nullifier_preimage = Bytes::from_slice(env, b"nullifier");
+        nullifier_preimage.append(&env.current_contract_address().to_xdr(env));
+        nullifier_preimage.append(&proof.secret.clone().into());
+        if env.crypto().sha256(&nullifier_preimage).to_bytes() != *nullifier {
+            return false;
+        }
+
+        let mut node = env.crypto().sha256(&proof.secret.clone().into()).to_bytes();
+        let mut index = proof.index;
+        for sibling in proof.path.iter() {
+            let mut preimage = Bytes::new(env);
+            if index & 1 == 0 {
+                preimage.append(&node.into());
+                preimage.append(&sibling.into());
+            } else {
+                preimage.append(&sibling.into());
+                preimage.append(&node.into());
+            }
+            node = env.crypto().sha256(&preimage).to_bytes();
+            index /= 2;
+        }
+
+        node == *root
+    }
+}
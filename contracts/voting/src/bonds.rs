@@ -0,0 +1,138 @@
+use soroban_sdk::{contractimpl, contracttype, token, vec, Address, Env, Symbol};
+
+use crate::{DataKey, Phase, VotingContract, VotingContractArgs, VotingContractClient};
+
+#[contracttype(export = false)]
+pub enum BondKey {
+    BondConfig,
+    Bond(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub amount: i128,
+    pub scale_weight: bool,
+}
+
+#[contractimpl]
+impl VotingContract {
+    pub fn vote_bonded(env: Env, voter: Address, party_name: Symbol, bond: i128) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        let config = Self::get_bond_config(env.clone()).expect("Bonded voting is not enabled");
+        let weight = Self::lock_bond(&env, &voter, &config, bond);
+        Self::cast_vote(&env, &voter, vec![&env, party_name], weight);
+    }
+
+    /// With `scale_weight` a voter's own ballot counts once per full `amount`
+    /// bonded. Delegators bond `amount` when they delegate and always count
+    /// once, whatever their delegate bonds.
+    pub fn enable_bonded_voting(env: Env, token: Address, amount: i128, scale_weight: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_bond_config(env, token, amount, scale_weight);
+    }
+
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&BondKey::BondConfig)
+    }
+
+    pub fn get_bond(env: Env, voter: Address) -> i128 {
+        env.storage().instance()
+            .get(&BondKey::Bond(voter))
+            .unwrap_or(0)
+    }
+
+    pub fn withdraw_bond(env: Env, voter: Address) -> i128 {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_phase(env.clone()) != Phase::Closed {
+            panic!("Bonds are locked until the deadline");
+        }
+
+        let bond = Self::get_bond(env.clone(), voter.clone());
+        if bond == 0 {
+            panic!("No bond to withdraw");
+        }
+
+        let config = Self::get_bond_config(env.clone()).expect("Bonded voting is not enabled");
+        env.storage().instance().remove(&BondKey::Bond(voter.clone()));
+        token::Client::new(&env, &config.token).transfer(&env.current_contract_address(), &voter, &bond);
+        bond
+    }
+
+    pub(crate) fn store_bond_config(env: Env, token: Address, amount: i128, scale_weight: bool) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if amount <= 0 {
+            panic!("Bond amount must be positive");
+        }
+
+        // Bonds are released at the deadline, so one must exist. Deadlines
+        // can only ever be moved, never cleared.
+        if Self::get_voting_deadline(env.clone()).is_none() {
+            panic!("Bonded voting requires a deadline");
+        }
+
+        env.storage().instance().set(&BondKey::BondConfig, &BondConfig {
+            token,
+            amount,
+            scale_weight,
+        });
+    }
+
+    /// Weight of a ballot cast without naming a bond: bonded elections take
+    /// the base amount from the voter.
+    pub(crate) fn ballot_weight(env: &Env, voter: &Address) -> u32 {
+        match Self::get_bond_config(env.clone()) {
+            Some(config) => Self::lock_bond(env, voter, &config, config.amount),
+            None => 1,
+        }
+    }
+
+    fn lock_bond(env: &Env, voter: &Address, config: &BondConfig, bond: i128) -> u32 {
+        if bond < config.amount {
+            panic!("Bond below required amount");
+        }
+
+        token::Client::new(env, &config.token).transfer(voter, &env.current_contract_address(), &bond);
+        let locked = Self::get_bond(env.clone(), voter.clone());
+        env.storage().instance().set(&BondKey::Bond(voter.clone()), &(locked + bond));
+
+        if config.scale_weight {
+            u32::try_from(bond / config.amount).expect("Bond too large")
+        } else {
+            1
+        }
+    }
+
+    /// In bonded elections a delegator bonds the base amount when delegating,
+    /// so the weight a delegate carries is backed by its owners' funds.
+    pub(crate) fn lock_delegation_bond(env: &Env, delegator: &Address) {
+        let Some(config) = Self::get_bond_config(env.clone()) else {
+            return;
+        };
+        if Self::get_bond(env.clone(), delegator.clone()) > 0 {
+            return;
+        }
+
+        token::Client::new(env, &config.token).transfer(delegator, &env.current_contract_address(), &config.amount);
+        env.storage().instance().set(&BondKey::Bond(delegator.clone()), &config.amount);
+    }
+
+    #[cfg(any(feature = "anonymous-ballots", feature = "signed-ballots"))]
+    pub(crate) fn require_unbonded(env: &Env) {
+        if env.storage().instance().has(&BondKey::BondConfig) {
+            panic!("Election uses bonded ballots");
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+use crate::{DataKey, Deadline, Phase, VoterStatus, VotingContract, VotingContractArgs, VotingContractClient};
+
+#[contracttype(export = false)]
+pub enum DelegationKey {
+    Delegators(Address),
+    TopicDelegation(Address, Symbol),
+    TopicDelegators(Address, Symbol),
+    DelegationExpiry(Address, Option<Symbol>),
+    Category,
+    RepresentedBy(Address),
+    RepresentedWeight(Address),
+    DelegateOverride,
+}
+
+#[contractimpl]
+impl VotingContract {
+    pub fn set_delegate_override(env: Env, enabled: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_delegate_override(env, enabled);
+    }
+
+    pub fn get_delegate_override(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DelegationKey::DelegateOverride)
+            .unwrap_or(false)
+    }
+
+    pub fn delegate(env: Env, delegator: Address, delegate_to: Address, topic: Option<Symbol>, expiry: Option<Deadline>) {
+        delegator.require_auth();
+        Self::require_not_paused(&env);
+
+        if delegator == delegate_to {
+            panic!("Cannot delegate to yourself");
+        }
+
+        if Self::get_phase(env.clone()) == Phase::Closed {
+            panic!("Voting period has ended");
+        }
+
+        if Self::anonymous_ballots(&env) {
+            panic!("Election uses anonymous ballots");
+        }
+
+        if let Some(expiry) = &expiry {
+            if expiry.has_passed(&env) {
+                panic!("Expiry must be in the future");
+            }
+        }
+
+        let category = match &topic {
+            Some(_) => topic.clone(),
+            None => Self::get_category(env.clone()),
+        };
+        Self::check_circular_delegation(&env, &delegator, &delegate_to, &category);
+        Self::register_if_eligible(&env, &delegator, true);
+        Self::register_if_eligible(&env, &delegate_to, false);
+
+        let delegator_status: VoterStatus = env.storage().instance()
+            .get(&DataKey::VoterStatus(delegator.clone()))
+            .unwrap_or(VoterStatus::NotRegistered);
+
+        let delegate_status: VoterStatus = env.storage().instance()
+            .get(&DataKey::VoterStatus(delegate_to.clone()))
+            .unwrap_or(VoterStatus::NotRegistered);
+
+        match delegator_status {
+            VoterStatus::Registered => {},
+            VoterStatus::NotRegistered => panic!("Delegator not registered"),
+            VoterStatus::Voted => panic!("Delegator already voted"),
+            VoterStatus::Delegated(_) if topic.is_none() && !Self::delegation_expired(&env, &delegator, &None) => {
+                panic!("Delegator already delegated")
+            },
+            VoterStatus::Delegated(_) => {},
+        }
+
+        if env.storage().instance().has(&DelegationKey::RepresentedBy(delegator.clone())) {
+            panic!("Delegator already voted");
+        }
+
+        match delegate_status {
+            VoterStatus::Registered => {},
+            VoterStatus::NotRegistered => panic!("Delegate not registered"),
+            VoterStatus::Voted => panic!("Cannot delegate to someone who already voted"),
+            VoterStatus::Delegated(_) if !Self::delegation_expired(&env, &delegate_to, &None) => {
+                panic!("Cannot delegate to someone who delegated")
+            },
+            VoterStatus::Delegated(_) => {},
+        }
+        Self::lock_delegation_bond(&env, &delegator);
+
+        if let Some(topic) = &topic {
+            if env.storage().instance().has(&DelegationKey::TopicDelegation(delegator.clone(), topic.clone()))
+                && !Self::delegation_expired(&env, &delegator, &Some(topic.clone())) {
+                panic!("Delegator already delegated");
+            }
+        }
+
+        match &expiry {
+            Some(expiry) => env.storage().instance().set(&DelegationKey::DelegationExpiry(delegator.clone(), topic.clone()), expiry),
+            None => env.storage().instance().remove(&DelegationKey::DelegationExpiry(delegator.clone(), topic.clone())),
+        }
+
+        if let Some(topic) = topic {
+            let mut delegators: Vec<Address> = env.storage().instance()
+                .get(&DelegationKey::TopicDelegators(delegate_to.clone(), topic.clone()))
+                .unwrap_or_else(|| Vec::new(&env));
+
+            delegators.push_back(delegator.clone());
+            env.storage().instance().set(&DelegationKey::TopicDelegators(delegate_to.clone(), topic.clone()), &delegators);
+            env.storage().instance().set(&DelegationKey::TopicDelegation(delegator, topic), &delegate_to);
+            return;
+        }
+
+        let mut delegators: Vec<Address> = env.storage().instance()
+            .get(&DelegationKey::Delegators(delegate_to.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        delegators.push_back(delegator.clone());
+        env.storage().instance().set(&DelegationKey::Delegators(delegate_to.clone()), &delegators);
+        env.storage().instance().set(&DataKey::VoterStatus(delegator), &VoterStatus::Delegated(delegate_to));
+    }
+
+    pub fn get_topic_delegate(env: Env, voter: Address, topic: Symbol) -> Option<Address> {
+        env.storage().instance().get(&DelegationKey::TopicDelegation(voter, topic))
+    }
+
+    pub fn get_delegation(env: Env, voter: Address) -> Option<(Address, Option<Deadline>)> {
+        if let Some(category) = Self::get_category(env.clone()) {
+            let topic_delegate: Option<Address> = env.storage().instance()
+                .get(&DelegationKey::TopicDelegation(voter.clone(), category.clone()));
+            if let Some(delegate) = topic_delegate {
+                let topic = Some(category);
+                if !Self::delegation_expired(&env, &voter, &topic) {
+                    return Some((delegate, env.storage().instance().get(&DelegationKey::DelegationExpiry(voter, topic))));
+                }
+            }
+        }
+
+        match Self::get_voter_status(env.clone(), voter.clone()) {
+            VoterStatus::Delegated(delegate) if !Self::delegation_expired(&env, &voter, &None) => {
+                Some((delegate, env.storage().instance().get(&DelegationKey::DelegationExpiry(voter, None))))
+            },
+            _ => None,
+        }
+    }
+
+    pub fn set_category(env: Env, category: Symbol) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_category(env, category);
+    }
+
+    pub fn get_category(env: Env) -> Option<Symbol> {
+        env.storage().instance().get(&DelegationKey::Category)
+    }
+
+    pub fn get_delegators(env: Env, delegate: Address, offset: u32, limit: u32) -> Vec<Address> {
+        let category = Self::get_category(env.clone());
+        let delegators = Self::active_delegators(&env, &delegate, &category);
+
+        let start = offset.min(delegators.len());
+        let end = offset.saturating_add(limit).min(delegators.len());
+        delegators.slice(start..end)
+    }
+
+    pub fn get_delegation_chain(env: Env, voter: Address) -> Vec<Address> {
+        let category = Self::get_category(env.clone());
+        let mut chain = Vec::new(&env);
+        let mut current = voter;
+
+        while let Some(next_delegate) = Self::active_delegate(&env, &current, &category) {
+            if chain.contains(&next_delegate) || chain.len() > 100 {
+                panic!("Delegation chain too long");
+            }
+            chain.push_back(next_delegate.clone());
+            current = next_delegate;
+        }
+
+        chain
+    }
+
+    pub fn get_delegated_power(env: Env, delegate: Address) -> u32 {
+        let category = Self::get_category(env.clone());
+        let represented_by: Option<Address> = match Self::get_voter_status(env.clone(), delegate.clone()) {
+            VoterStatus::Voted => Some(delegate.clone()),
+            _ => env.storage().instance().get(&DelegationKey::RepresentedBy(delegate.clone())),
+        };
+        let represented = Self::represented_voters(&env, &delegate, &category, &represented_by);
+        Self::represented_weights(&env, &represented).iter().sum()
+    }
+
+    pub(crate) fn store_delegate_override(env: Env, enabled: bool) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&DelegationKey::DelegateOverride, &enabled);
+    }
+
+    pub(crate) fn store_category(env: Env, category: Symbol) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&DelegationKey::Category, &category);
+    }
+
+    pub(crate) fn represented_weights(env: &Env, represented: &Vec<Address>) -> Vec<u32> {
+        let mut weights = Vec::new(env);
+        for delegator in represented.iter() {
+            weights.push_back(Self::oracle_weight(env, &delegator));
+        }
+        weights
+    }
+
+    fn delegation_expired(env: &Env, voter: &Address, topic: &Option<Symbol>) -> bool {
+        env.storage().instance()
+            .get::<DelegationKey, Deadline>(&DelegationKey::DelegationExpiry(voter.clone(), topic.clone()))
+            .is_some_and(|expiry| expiry.has_passed(env))
+    }
+
+    pub(crate) fn active_delegate(env: &Env, voter: &Address, category: &Option<Symbol>) -> Option<Address> {
+        if let Some(category) = category {
+            let topic_delegate: Option<Address> = env.storage().instance()
+                .get(&DelegationKey::TopicDelegation(voter.clone(), category.clone()));
+            if topic_delegate.is_some() && !Self::delegation_expired(env, voter, &Some(category.clone())) {
+                return topic_delegate;
+            }
+        }
+
+        let status: VoterStatus = env.storage().instance()
+            .get(&DataKey::VoterStatus(voter.clone()))
+            .unwrap_or(VoterStatus::NotRegistered);
+
+        match status {
+            VoterStatus::Delegated(delegate) if !Self::delegation_expired(env, voter, &None) => Some(delegate),
+            _ => None,
+        }
+    }
+
+    fn active_delegators(env: &Env, delegate: &Address, category: &Option<Symbol>) -> Vec<Address> {
+        let mut candidates: Vec<Address> = env.storage().instance()
+            .get(&DelegationKey::Delegators(delegate.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+
+        if let Some(category) = category {
+            let topic_delegators: Vec<Address> = env.storage().instance()
+                .get(&DelegationKey::TopicDelegators(delegate.clone(), category.clone()))
+                .unwrap_or_else(|| Vec::new(env));
+            candidates.append(&topic_delegators);
+        }
+
+        let mut delegators = Vec::new(env);
+        for candidate in candidates.iter() {
+            if delegators.contains(&candidate) {
+                continue;
+            }
+
+            let status: VoterStatus = env.storage().instance()
+                .get(&DataKey::VoterStatus(candidate.clone()))
+                .unwrap_or(VoterStatus::NotRegistered);
+
+            if status != VoterStatus::Voted && Self::active_delegate(env, &candidate, category) == Some(delegate.clone()) {
+                delegators.push_back(candidate);
+            }
+        }
+        delegators
+    }
+
+    pub(crate) fn represented_voters(env: &Env, delegate: &Address, category: &Option<Symbol>, represented_by: &Option<Address>) -> Vec<Address> {
+        let mut represented = Vec::new(env);
+        for delegator in Self::active_delegators(env, delegate, category).iter() {
+            let delegator_represented_by: Option<Address> = env.storage().instance()
+                .get(&DelegationKey::RepresentedBy(delegator.clone()));
+            if delegator_represented_by != *represented_by {
+                continue;
+            }
+
+            represented.append(&Self::represented_voters(env, &delegator, category, represented_by));
+            if Self::oracle_allows(env, &delegator) {
+                represented.push_back(delegator);
+            }
+        }
+        represented
+    }
+
+    fn check_circular_delegation(env: &Env, delegator: &Address, delegate_to: &Address, category: &Option<Symbol>) {
+        let mut current = delegate_to.clone();
+        let mut visited = Vec::new(env);
+        
+        loop {
+            if visited.contains(&current) {
+                panic!("Circular delegation detected");
+            }
+            
+            visited.push_back(current.clone());
+            
+            if current == *delegator {
+                panic!("Circular delegation detected");
+            }
+            
+            let status: VoterStatus = env.storage().instance()
+                .get(&DataKey::VoterStatus(current.clone()))
+                .unwrap_or(VoterStatus::NotRegistered);
+
+            if status == VoterStatus::Voted || env.storage().instance().has(&DelegationKey::RepresentedBy(current.clone())) {
+                panic!("Cannot delegate to someone who already voted");
+            }
+
+            match Self::active_delegate(env, &current, category) {
+                Some(next_delegate) => {
+                    current = next_delegate;
+                },
+                None => break,
+            }
+            
+            if visited.len() > 100 {
+                panic!("Delegation chain too long");
+            }
+        }
+    }
+}
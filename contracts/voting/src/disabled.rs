@@ -0,0 +1,128 @@
+//! Stand-ins for the hooks the core calls into optional modules, used when a
+//! module's feature is off. Checks pass as if the module were never
+//! configured, and admin actions that would configure it are rejected.
+
+#[cfg(not(feature = "encrypted-ballots"))]
+mod encrypted {
+    use soroban_sdk::{Address, BytesN, Env};
+
+    use crate::VotingContract;
+
+    impl VotingContract {
+        pub(crate) fn encrypted_ballots(_env: &Env) -> bool {
+            false
+        }
+
+        pub(crate) fn tally_pending(_env: &Env) -> bool {
+            false
+        }
+
+        pub(crate) fn store_encryption_key(_env: Env, _trustee: Address, _public_key_g1: BytesN<96>, _public_key_g2: BytesN<192>) {
+            panic!("Encrypted ballots are not supported by this build");
+        }
+    }
+}
+
+#[cfg(not(feature = "anonymous-ballots"))]
+mod anonymous {
+    use soroban_sdk::{BytesN, Env};
+
+    use crate::VotingContract;
+
+    impl VotingContract {
+        pub(crate) fn anonymous_ballots(_env: &Env) -> bool {
+            false
+        }
+
+        pub(crate) fn store_voter_root(_env: Env, _root: BytesN<32>) {
+            panic!("Anonymous ballots are not supported by this build");
+        }
+    }
+}
+
+#[cfg(not(feature = "bonds"))]
+mod bonds {
+    use soroban_sdk::{Address, Env};
+
+    use crate::VotingContract;
+
+    impl VotingContract {
+        pub(crate) fn ballot_weight(_env: &Env, _voter: &Address) -> u32 {
+            1
+        }
+
+        pub(crate) fn lock_delegation_bond(_env: &Env, _delegator: &Address) {}
+
+        #[cfg(any(feature = "anonymous-ballots", feature = "signed-ballots"))]
+        pub(crate) fn require_unbonded(_env: &Env) {}
+
+        pub(crate) fn store_bond_config(_env: Env, _token: Address, _amount: i128, _scale_weight: bool) {
+            panic!("Bonded voting is not supported by this build");
+        }
+    }
+}
+
+#[cfg(not(feature = "eligibility"))]
+mod eligibility {
+    use soroban_sdk::{Address, Env};
+
+    use crate::VotingContract;
+
+    impl VotingContract {
+        pub(crate) fn register_if_eligible(_env: &Env, _voter: &Address, _can_deposit: bool) {}
+
+        pub(crate) fn oracle_allows(_env: &Env, _voter: &Address) -> bool {
+            true
+        }
+
+        pub(crate) fn oracle_weight(_env: &Env, _voter: &Address) -> u32 {
+            1
+        }
+
+        pub(crate) fn store_eligibility_token(_env: Env, _token: Address, _min_balance: i128) {
+            panic!("Eligibility checks are not supported by this build");
+        }
+
+        pub(crate) fn store_eligibility_oracle(_env: Env, _oracle: Address) {
+            panic!("Eligibility checks are not supported by this build");
+        }
+    }
+}
+
+#[cfg(not(feature = "proposals"))]
+mod governance {
+    use soroban_sdk::{Env, Symbol, Vec};
+
+    use crate::{Invocation, VotingContract};
+
+    impl VotingContract {
+        pub(crate) fn store_proposal(_env: Env, _party_name: Symbol, _invocations: Vec<Invocation>) {
+            panic!("Proposals are not supported by this build");
+        }
+
+        pub(crate) fn store_execution_delay(_env: Env, _delay: u64) {
+            panic!("Proposals are not supported by this build");
+        }
+
+        pub(crate) fn store_proposal_threshold(_env: Env, _quorum: u32, _majority_bps: u32) {
+            panic!("Proposals are not supported by this build");
+        }
+    }
+}
+
+#[cfg(not(feature = "rewards"))]
+mod rewards {
+    use soroban_sdk::{Address, Env};
+
+    use crate::VotingContract;
+
+    impl VotingContract {
+        pub(crate) fn store_reward_pool(_env: Env, _token: Address, _amount_per_vote: i128, _claim_window: u64) {
+            panic!("Rewards are not supported by this build");
+        }
+
+        pub(crate) fn release_rewards(_env: Env) -> i128 {
+            panic!("Rewards are not supported by this build");
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use soroban_sdk::{contractclient, contractimpl, contracttype, token, Address, Env, Symbol, Vec};
+
+use crate::{DataKey, Phase, VoterStatus, VotingContract, VotingContractArgs, VotingContractClient};
+
+#[contracttype(export = false)]
+pub enum EligibilityKey {
+    Token,
+    Oracle,
+    Deposit(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EligibilityToken {
+    pub token: Address,
+    pub min_balance: i128,
+}
+
+#[contractclient(name = "EligibilityOracleClient")]
+pub trait EligibilityOracle {
+    fn is_eligible(env: Env, voter: Address, election: Address) -> bool;
+    fn weight(env: Env, voter: Address, election: Address) -> i128;
+}
+
+#[contractimpl]
+impl VotingContract {
+    /// Holders of at least `min_balance` of the fungible `token` are registered
+    /// on their first vote or delegation, or through `register`. Registering
+    /// escrows `min_balance` until the deadline, so the same tokens cannot make
+    /// a second account eligible. NFT collections have no amount-based
+    /// transfer to escrow with and are rejected.
+    pub fn set_eligibility_token(env: Env, token: Address, min_balance: i128) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_eligibility_token(env, token, min_balance);
+    }
+
+    pub fn get_eligibility_token(env: Env) -> Option<EligibilityToken> {
+        env.storage().instance().get(&EligibilityKey::Token)
+    }
+
+    /// Registers a token holder ahead of voting, e.g. before relaying ballots
+    /// or being delegated to, by escrowing the gate's minimum balance.
+    pub fn register(env: Env, voter: Address) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_phase(env.clone()) == Phase::Closed {
+            panic!("Voting period has ended");
+        }
+
+        if Self::get_voter_status(env.clone(), voter.clone()) != VoterStatus::NotRegistered {
+            panic!("Voter already registered");
+        }
+
+        Self::register_if_eligible(&env, &voter, true);
+        if Self::get_voter_status(env.clone(), voter) == VoterStatus::NotRegistered {
+            panic!("Voter not eligible");
+        }
+    }
+
+    pub fn get_eligibility_deposit(env: Env, voter: Address) -> i128 {
+        env.storage().instance()
+            .get(&EligibilityKey::Deposit(voter))
+            .unwrap_or(0)
+    }
+
+    pub fn withdraw_eligibility_deposit(env: Env, voter: Address) -> i128 {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_phase(env.clone()) != Phase::Closed {
+            panic!("Deposits are locked until the deadline");
+        }
+
+        let deposit = Self::get_eligibility_deposit(env.clone(), voter.clone());
+        if deposit == 0 {
+            panic!("No deposit to withdraw");
+        }
+
+        let gate = Self::get_eligibility_token(env.clone()).expect("Token gate is not enabled");
+        env.storage().instance().remove(&EligibilityKey::Deposit(voter.clone()));
+        token::Client::new(&env, &gate.token).transfer(&env.current_contract_address(), &voter, &deposit);
+        deposit
+    }
+
+    /// The oracle is asked on every ballot, so revoking a registered voter takes
+    /// effect immediately; its weight applies to the voter's own ballot.
+    pub fn set_eligibility_oracle(env: Env, oracle: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_eligibility_oracle(env, oracle);
+    }
+
+    pub fn get_eligibility_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&EligibilityKey::Oracle)
+    }
+
+    pub(crate) fn store_eligibility_token(env: Env, token: Address, min_balance: i128) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if min_balance <= 0 {
+            panic!("Minimum balance must be positive");
+        }
+
+        if Self::get_voting_deadline(env.clone()).is_none() {
+            panic!("Token-gated voting requires a deadline");
+        }
+
+        let decimals = env.try_invoke_contract::<u32, soroban_sdk::InvokeError>(&token, &Symbol::new(&env, "decimals"), Vec::new(&env));
+        if !matches!(decimals, Ok(Ok(_))) {
+            panic!("Eligibility token must be a fungible token");
+        }
+
+        env.storage().instance().set(&EligibilityKey::Token, &EligibilityToken {
+            token,
+            min_balance,
+        });
+    }
+
+    pub(crate) fn store_eligibility_oracle(env: Env, oracle: Address) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&EligibilityKey::Oracle, &oracle);
+    }
+
+    /// Token holders are registered by escrowing the minimum balance until the
+    /// deadline, so the same tokens cannot qualify a second account. That
+    /// needs the voter's authorization, which callers signal with `can_deposit`.
+    pub(crate) fn register_if_eligible(env: &Env, voter: &Address, can_deposit: bool) {
+        let gate = Self::get_eligibility_token(env.clone());
+        if gate.is_none() && Self::get_eligibility_oracle(env.clone()).is_none() {
+            return;
+        }
+
+        if env.storage().instance().has(&DataKey::VoterStatus(voter.clone())) {
+            return;
+        }
+
+        if let Some(gate) = &gate {
+            if !can_deposit || token::Client::new(env, &gate.token).balance(voter) < gate.min_balance {
+                return;
+            }
+        }
+
+        if !Self::oracle_allows(env, voter) {
+            return;
+        }
+
+        if let Some(gate) = gate {
+            token::Client::new(env, &gate.token).transfer(voter, &env.current_contract_address(), &gate.min_balance);
+            env.storage().instance().set(&EligibilityKey::Deposit(voter.clone()), &gate.min_balance);
+        }
+        Self::register_voter(env, voter);
+    }
+
+    pub(crate) fn oracle_allows(env: &Env, voter: &Address) -> bool {
+        match Self::get_eligibility_oracle(env.clone()) {
+            Some(oracle) => EligibilityOracleClient::new(env, &oracle).is_eligible(voter, &env.current_contract_address()),
+            None => true,
+        }
+    }
+
+    pub(crate) fn oracle_weight(env: &Env, voter: &Address) -> u32 {
+        let Some(oracle) = Self::get_eligibility_oracle(env.clone()) else {
+            return 1;
+        };
+
+        let weight = EligibilityOracleClient::new(env, &oracle).weight(voter, &env.current_contract_address());
+        match u32::try_from(weight) {
+            Ok(weight) if weight > 0 => weight,
+            _ => panic!("Invalid voter weight"),
+        }
+    }
+}
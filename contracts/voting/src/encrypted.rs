@@ -0,0 +1,263 @@
+use soroban_sdk::{
+    contractimpl, contracttype, crypto::bls12_381::{Fr, G1Affine, G2Affine}, vec, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Symbol, Vec, U256,
+};
+
+use crate::{DataKey, Phase, VotingContract, VotingContractArgs, VotingContractClient};
+
+pub(crate) const G1_IDENTITY: [u8; 96] = {
+    let mut bytes = [0u8; 96];
+    bytes[0] = 0x40;
+    bytes
+};
+
+const ENCRYPTION_DST: &[u8] = b"STELLAR-VOTE-V01-CS01-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+const PAIRING_DST: &[u8] = b"STELLAR-VOTE-V01-CS01-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+const PROOF_DOMAIN: &[u8] = b"stellar-vote:ballot-proof:v1";
+
+/// Fixed generators for exponential ElGamal ballots: ciphertexts live in G1 and
+/// the trustee's G2 key is taken relative to the second point, so decryption
+/// shares can be checked with a single pairing equation.
+pub fn encryption_generators(env: &Env) -> (G1Affine, G2Affine) {
+    let bls = env.crypto().bls12_381();
+    let message = Bytes::from_slice(env, b"generator");
+    (
+        bls.hash_to_g1(&message, &Bytes::from_slice(env, ENCRYPTION_DST)),
+        bls.hash_to_g2(&message, &Bytes::from_slice(env, PAIRING_DST)),
+    )
+}
+
+/// Fiat-Shamir challenge for encrypted ballot proofs, bound to the election
+/// and the voter so a proof cannot be replayed elsewhere.
+pub fn proof_challenge(env: &Env, election: &Address, voter: &Address, points: &[G1Affine]) -> Fr {
+    let mut preimage = Bytes::from_slice(env, PROOF_DOMAIN);
+    preimage.append(&(election.clone(), voter.clone()).to_xdr(env));
+    for point in points {
+        preimage.append(&point.to_bytes().into());
+    }
+    Fr::from_u256(U256::from_be_bytes(env, &env.crypto().sha256(&preimage).to_bytes().into()))
+}
+
+#[contracttype(export = false)]
+pub enum EncryptedBallotKey {
+    EncryptionKey,
+    EncryptedTally(Symbol),
+    TallyPublished,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncryptionKey {
+    pub trustee: Address,
+    pub public_key_g1: BytesN<96>,
+    pub public_key_g2: BytesN<192>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ciphertext {
+    pub c1: BytesN<96>,
+    pub c2: BytesN<96>,
+}
+
+/// Chaum-Pedersen proof that `(c1, c2 - m * G)` is `(r * G, r * public_key)`
+/// for some `r`, i.e. that the ciphertext encrypts `m`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EqualityProof {
+    pub challenge: U256,
+    pub response: U256,
+}
+
+/// Disjunctive proof that a ciphertext encrypts either zero or the voter's
+/// full weight, without revealing which.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChoiceProof {
+    pub zero: EqualityProof,
+    pub weight: EqualityProof,
+}
+
+#[contractimpl]
+impl VotingContract {
+    pub fn enable_encrypted_ballots(env: Env, trustee: Address, public_key_g1: BytesN<96>, public_key_g2: BytesN<192>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_encryption_key(env, trustee, public_key_g1, public_key_g2);
+    }
+
+    pub fn get_encryption_key(env: Env) -> Option<EncryptionKey> {
+        env.storage().instance().get(&EncryptedBallotKey::EncryptionKey)
+    }
+
+    /// `ballot` holds one ciphertext per party. Each comes with a proof that it
+    /// encrypts zero or the voter's weight, and `sum_proof` shows the entries
+    /// add up to that weight, so exactly one party receives it.
+    pub fn vote_encrypted(env: Env, voter: Address, ballot: Vec<Ciphertext>, proofs: Vec<ChoiceProof>, sum_proof: EqualityProof) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        let key = Self::get_encryption_key(env.clone())
+            .expect("Encrypted ballots are not enabled");
+
+        let (represented, represented_by) = Self::open_ballot(&env, &voter);
+        if represented_by.is_some() {
+            panic!("Encrypted ballots cannot override a delegate");
+        }
+
+        let parties = Self::get_parties(env.clone());
+        if ballot.len() != parties.len() || proofs.len() != parties.len() {
+            panic!("Ballot must contain one ciphertext per party");
+        }
+
+        let own_weight = Self::ballot_weight(&env, &voter);
+        let weights = Self::represented_weights(&env, &represented);
+        let weight = Self::voting_power(&env, &voter, own_weight, &weights);
+
+        let bls = env.crypto().bls12_381();
+        let (generator, _) = encryption_generators(&env);
+        let public_key = G1Affine::from_bytes(key.public_key_g1);
+        let election = env.current_contract_address();
+        let weight_point = bls.g1_mul(&generator, &Fr::from_u256(U256::from_u32(&env, weight)));
+
+        let mut c1_sum = G1Affine::from_bytes(BytesN::from_array(&env, &G1_IDENTITY));
+        let mut c2_sum = G1Affine::from_bytes(BytesN::from_array(&env, &G1_IDENTITY));
+        for (ciphertext, proof) in ballot.iter().zip(proofs.iter()) {
+            let c1 = G1Affine::from_bytes(ciphertext.c1);
+            let c2 = G1Affine::from_bytes(ciphertext.c2);
+            c1_sum = bls.g1_checked_add(&c1_sum, &c1).expect("Invalid ciphertext");
+            c2_sum = bls.g1_checked_add(&c2_sum, &c2).expect("Invalid ciphertext");
+
+            let c2_minus_weight = bls.g1_add(&c2, &-&weight_point);
+            let (a0, b0) = Self::equality_commitments(&env, &proof.zero, &public_key, &c1, &c2);
+            let (a1, b1) = Self::equality_commitments(&env, &proof.weight, &public_key, &c1, &c2_minus_weight);
+            let challenge = proof_challenge(&env, &election, &voter, &[c1, c2, a0, b0, a1, b1]);
+            if Fr::from_u256(proof.zero.challenge) + Fr::from_u256(proof.weight.challenge) != challenge {
+                panic!("Invalid ballot proof");
+            }
+        }
+
+        let c2_minus_weight = bls.g1_add(&c2_sum, &-&weight_point);
+        let (a, b) = Self::equality_commitments(&env, &sum_proof, &public_key, &c1_sum, &c2_minus_weight);
+        let challenge = proof_challenge(&env, &election, &voter, &[c1_sum, c2_minus_weight, a, b]);
+        if Fr::from_u256(sum_proof.challenge) != challenge {
+            panic!("Ballot does not encrypt the voter's weight");
+        }
+
+        for (party, ciphertext) in parties.iter().zip(ballot.iter()) {
+            let tally = match env.storage().instance().get::<EncryptedBallotKey, Ciphertext>(&EncryptedBallotKey::EncryptedTally(party.clone())) {
+                Some(tally) => Ciphertext {
+                    c1: bls.g1_add(&G1Affine::from_bytes(tally.c1), &G1Affine::from_bytes(ciphertext.c1)).to_bytes(),
+                    c2: bls.g1_add(&G1Affine::from_bytes(tally.c2), &G1Affine::from_bytes(ciphertext.c2)).to_bytes(),
+                },
+                None => ciphertext,
+            };
+            env.storage().instance().set(&EncryptedBallotKey::EncryptedTally(party), &tally);
+        }
+
+        env.storage().instance().set(&DataKey::Turnout, &(Self::turnout(&env) + weight));
+        Self::close_ballot(&env, &voter, &represented, &weights);
+    }
+
+    pub fn get_encrypted_tally(env: Env, party_name: Symbol) -> Option<Ciphertext> {
+        env.storage().instance().get(&EncryptedBallotKey::EncryptedTally(party_name))
+    }
+
+    pub fn publish_tally(env: Env, tally: Vec<u32>, decryption_shares: Vec<BytesN<96>>) {
+        let key = Self::get_encryption_key(env.clone())
+            .expect("Encrypted ballots are not enabled");
+        key.trustee.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::get_phase(env.clone()) != Phase::Closed {
+            panic!("Voting period has not ended");
+        }
+
+        if env.storage().instance().has(&EncryptedBallotKey::TallyPublished) {
+            panic!("Tally already published");
+        }
+
+        let parties = Self::get_parties(env.clone());
+        if tally.len() != parties.len() || decryption_shares.len() != parties.len() {
+            panic!("Tally must contain one entry per party");
+        }
+
+        let bls = env.crypto().bls12_381();
+        let (generator, pairing_base) = encryption_generators(&env);
+        let public_key_g2 = G2Affine::from_bytes(key.public_key_g2);
+
+        for i in 0..parties.len() {
+            let party = parties.get_unchecked(i);
+            let votes = tally.get_unchecked(i);
+
+            match Self::get_encrypted_tally(env.clone(), party.clone()) {
+                Some(ciphertext) => {
+                    let share = G1Affine::from_bytes(decryption_shares.get_unchecked(i));
+                    let c1 = G1Affine::from_bytes(ciphertext.c1);
+                    let share_valid = bls.pairing_check(
+                        vec![&env, share.clone(), -&c1],
+                        vec![&env, pairing_base.clone(), public_key_g2.clone()],
+                    );
+                    let plaintext = bls.g1_add(&bls.g1_mul(&generator, &Fr::from_u256(U256::from_u32(&env, votes))), &share);
+                    if !share_valid || plaintext.to_bytes() != ciphertext.c2 {
+                        panic!("Invalid decryption proof");
+                    }
+                },
+                None if votes != 0 => panic!("Invalid decryption proof"),
+                None => {},
+            }
+
+            env.storage().instance().set(&DataKey::Votes(party), &votes);
+        }
+
+        env.storage().instance().set(&EncryptedBallotKey::TallyPublished, &true);
+    }
+
+    pub(crate) fn encrypted_ballots(env: &Env) -> bool {
+        env.storage().instance().has(&EncryptedBallotKey::EncryptionKey)
+    }
+
+    /// Encrypted elections can only be finalized once the trustee has
+    /// published the tally.
+    pub(crate) fn tally_pending(env: &Env) -> bool {
+        Self::encrypted_ballots(env) && !env.storage().instance().has(&EncryptedBallotKey::TallyPublished)
+    }
+
+    pub(crate) fn store_encryption_key(env: Env, trustee: Address, public_key_g1: BytesN<96>, public_key_g2: BytesN<192>) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        let bls = env.crypto().bls12_381();
+        let (generator, pairing_base) = encryption_generators(&env);
+        let key_matches = bls.pairing_check(
+            vec![&env, G1Affine::from_bytes(public_key_g1.clone()), -&generator],
+            vec![&env, pairing_base, G2Affine::from_bytes(public_key_g2.clone())],
+        );
+        if !key_matches {
+            panic!("Invalid encryption key");
+        }
+
+        env.storage().instance().set(&EncryptedBallotKey::EncryptionKey, &EncryptionKey {
+            trustee,
+            public_key_g1,
+            public_key_g2,
+        });
+    }
+
+    /// Recomputes the prover's commitments for `proof` over `(c1, c2)`.
+    pub(crate) fn equality_commitments(env: &Env, proof: &EqualityProof, public_key: &G1Affine, c1: &G1Affine, c2: &G1Affine) -> (G1Affine, G1Affine) {
+        let bls = env.crypto().bls12_381();
+        let (generator, _) = encryption_generators(env);
+        let challenge = Fr::from_u256(proof.challenge.clone());
+        let response = Fr::from_u256(proof.response.clone());
+        (
+            bls.g1_add(&bls.g1_mul(&generator, &response), &-&bls.g1_mul(c1, &challenge)),
+            bls.g1_add(&bls.g1_mul(public_key, &response), &-&bls.g1_mul(c2, &challenge)),
+        )
+    }
+}
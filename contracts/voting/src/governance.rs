@@ -0,0 +1,155 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Val, Vec};
+
+use crate::{DataKey, Invocation, VotingContract, VotingContractArgs, VotingContractClient, ABSTAIN, NOTA};
+
+#[contracttype(export = false)]
+pub enum GovernanceKey {
+    Proposal(Symbol),
+    ProposalExecuted(Symbol),
+    ExecutionDelay,
+    ProposalThreshold,
+}
+
+/// `quorum` is the minimum turnout, in votes; the winning proposal also needs
+/// more than `majority_bps` basis points of that turnout.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProposalThreshold {
+    pub quorum: u32,
+    pub majority_bps: u32,
+}
+
+#[contractimpl]
+impl VotingContract {
+    /// Attaches the calls to run if `party_name` wins; the party symbol doubles
+    /// as the proposal id.
+    pub fn set_proposal(env: Env, party_name: Symbol, invocations: Vec<Invocation>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_proposal(env, party_name, invocations);
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: Symbol) -> Option<Vec<Invocation>> {
+        env.storage().instance().get(&GovernanceKey::Proposal(proposal_id))
+    }
+
+    /// Seconds between finalization and the earliest execution of the winning proposal.
+    pub fn set_execution_delay(env: Env, delay: u64) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_execution_delay(env, delay);
+    }
+
+    pub fn get_execution_delay(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&GovernanceKey::ExecutionDelay)
+            .unwrap_or(0)
+    }
+
+    pub fn set_proposal_threshold(env: Env, quorum: u32, majority_bps: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_proposal_threshold(env, quorum, majority_bps);
+    }
+
+    pub fn get_proposal_threshold(env: Env) -> ProposalThreshold {
+        env.storage().instance()
+            .get(&GovernanceKey::ProposalThreshold)
+            .unwrap_or(ProposalThreshold { quorum: 0, majority_bps: 0 })
+    }
+
+    /// Runs the winning proposal recorded at finalization. The winner must be
+    /// unique and meet the proposal threshold.
+    pub fn execute(env: Env, proposal_id: Symbol) {
+        Self::require_not_paused(&env);
+
+        let invocations = Self::get_proposal(env.clone(), proposal_id.clone())
+            .expect("Proposal not found");
+        let result = Self::get_final_result(env.clone())
+            .expect("Election not finalized");
+
+        if result.winner != Some(proposal_id.clone()) {
+            panic!("Proposal did not pass");
+        }
+
+        let winning_votes = result.results.get(proposal_id.clone()).unwrap_or(0);
+        let nota_competes = Self::get_nota_competes(env.clone());
+        for (choice, votes) in result.results.iter() {
+            if choice != proposal_id && choice != ABSTAIN && (choice != NOTA || nota_competes) && votes == winning_votes {
+                panic!("Proposal is tied");
+            }
+        }
+
+        let threshold = Self::get_proposal_threshold(env.clone());
+        let turnout = Self::turnout(&env) as u64;
+        if turnout < threshold.quorum as u64 {
+            panic!("Quorum not reached");
+        }
+
+        if winning_votes as u64 * 10_000 <= threshold.majority_bps as u64 * turnout {
+            panic!("Majority not reached");
+        }
+
+        if env.ledger().timestamp() < result.finalized_at + Self::get_execution_delay(env.clone()) {
+            panic!("Timelock has not expired");
+        }
+
+        if Self::is_proposal_executed(env.clone(), proposal_id.clone()) {
+            panic!("Proposal already executed");
+        }
+
+        env.storage().instance().set(&GovernanceKey::ProposalExecuted(proposal_id.clone()), &true);
+        for invocation in invocations.iter() {
+            env.invoke_contract::<Val>(&invocation.contract, &invocation.function, invocation.args);
+        }
+
+        env.events().publish((symbol_short!("executed"),), proposal_id);
+    }
+
+    pub fn is_proposal_executed(env: Env, proposal_id: Symbol) -> bool {
+        env.storage().instance()
+            .get(&GovernanceKey::ProposalExecuted(proposal_id))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn store_proposal(env: Env, party_name: Symbol, invocations: Vec<Invocation>) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if !Self::get_parties(env.clone()).contains(&party_name) {
+            panic!("Party not found");
+        }
+
+        env.storage().instance().set(&GovernanceKey::Proposal(party_name), &invocations);
+    }
+
+    pub(crate) fn store_execution_delay(env: Env, delay: u64) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&GovernanceKey::ExecutionDelay, &delay);
+    }
+
+    pub(crate) fn store_proposal_threshold(env: Env, quorum: u32, majority_bps: u32) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if majority_bps >= 10_000 {
+            panic!("Majority must be below 10000 basis points");
+        }
+
+        env.storage().instance().set(&GovernanceKey::ProposalThreshold, &ProposalThreshold { quorum, majority_bps });
+    }
+}
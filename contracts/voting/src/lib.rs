@@ -1,20 +1,78 @@
 #![no_std]
 
-use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Env, Map, Symbol, Vec,
-};
-
-
-#[contracttype]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, vec, Address, Env, IntoVal, Map, String, Symbol, Val, Vec};
+use voting_interface::{FinalResult, VotingReader, VOTING_READER_VERSION};
+
+mod admin;
+#[cfg(feature = "anonymous-ballots")]
+mod anonymous;
+#[cfg(feature = "bonds")]
+mod bonds;
+mod delegation;
+mod disabled;
+#[cfg(feature = "eligibility")]
+mod eligibility;
+#[cfg(feature = "encrypted-ballots")]
+mod encrypted;
+#[cfg(feature = "proposals")]
+mod governance;
+#[cfg(feature = "signed-ballots")]
+mod relay;
+#[cfg(feature = "rewards")]
+mod rewards;
+mod seats;
+
+pub use admin::{AdminAction, Council, CouncilAction, ScheduledAction};
+#[cfg(feature = "anonymous-ballots")]
+pub use anonymous::MembershipProof;
+#[cfg(feature = "bonds")]
+pub use bonds::BondConfig;
+#[cfg(feature = "eligibility")]
+pub use eligibility::{EligibilityOracle, EligibilityToken};
+#[cfg(feature = "encrypted-ballots")]
+pub use encrypted::{encryption_generators, proof_challenge, ChoiceProof, Ciphertext, EncryptionKey, EqualityProof};
+#[cfg(feature = "proposals")]
+pub use governance::ProposalThreshold;
+#[cfg(feature = "signed-ballots")]
+pub use relay::{BallotResult, SignedBallot};
+#[cfg(feature = "rewards")]
+pub use rewards::RewardPool;
+
+use delegation::DelegationKey;
+use seats::SeatKey;
+
+pub const SCHEMA_VERSION: u32 = 2;
+
+const MAX_SUBSCRIBERS: u32 = 10;
+
+pub const ABSTAIN: Symbol = symbol_short!("ABSTAIN");
+pub const NOTA: Symbol = symbol_short!("NOTA");
+
+// Each feature module keeps its own storage-key enum. Keys from all of them
+// share one storage namespace, so variant names must not repeat across enums.
+#[contracttype(export = false)]
 pub enum DataKey {
     Admin,
     Parties,
     Voters,
     Votes(Symbol),
     VoterStatus(Address),
-    DelegatedVotes(Address),
+    Ballot(Address),
+    NotaCompetes,
+    HideResults,
+    Finalized,
+    FinalResult,
+    Subscribers,
+    VotingStart,
     VotingDeadline,
+    VotingStarted,
+    BaseDeadline,
+    MaxDeadlineExtension,
+    DeadlineHistory,
+    Turnout,
     TotalVoters,
+    SchemaVersion,
+    DelegatedVotes(Address),
 }
 
 #[contracttype]
@@ -26,13 +84,104 @@ pub enum VoterStatus {
     Delegated(Address),
 }
 
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Deadline {
+    Timestamp(u64),
+    Ledger(u32),
+}
+
+impl Deadline {
+    pub fn has_passed(&self, env: &Env) -> bool {
+        match self {
+            Deadline::Timestamp(timestamp) => env.ledger().timestamp() > *timestamp,
+            Deadline::Ledger(sequence) => env.ledger().sequence() > *sequence,
+        }
+    }
+
+    pub fn is_reached(&self, env: &Env) -> bool {
+        match self {
+            Deadline::Timestamp(timestamp) => env.ledger().timestamp() >= *timestamp,
+            Deadline::Ledger(sequence) => env.ledger().sequence() >= *sequence,
+        }
+    }
+
+    pub fn extension_from(&self, previous: &Deadline) -> Option<u64> {
+        match (previous, self) {
+            (Deadline::Timestamp(previous), Deadline::Timestamp(next)) if next > previous => Some(next - previous),
+            (Deadline::Ledger(previous), Deadline::Ledger(next)) if next > previous => Some((next - previous) as u64),
+            _ => None,
+        }
+    }
+
+    fn value(&self) -> u64 {
+        match self {
+            Deadline::Timestamp(timestamp) => *timestamp,
+            Deadline::Ledger(sequence) => *sequence as u64,
+        }
+    }
+
+    fn current(&self, env: &Env) -> u64 {
+        match self {
+            Deadline::Timestamp(_) => env.ledger().timestamp(),
+            Deadline::Ledger(_) => env.ledger().sequence() as u64,
+        }
+    }
+
+    fn shifted(&self, by: u64) -> Deadline {
+        match self {
+            Deadline::Timestamp(timestamp) => Deadline::Timestamp(timestamp.saturating_add(by)),
+            Deadline::Ledger(sequence) => Deadline::Ledger(sequence.saturating_add(by as u32)),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Pending,
+    Open,
+    Closed,
+}
+
+// soroban-sdk 22 cannot encode `Option<Deadline>` as a struct field.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreviousDeadline {
+    Unset,
+    Set(Deadline),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeadlineChange {
+    pub previous: PreviousDeadline,
+    pub deadline: Deadline,
+    pub reason: String,
+    pub changed_at: u64,
+}
+
 #[contracttype]
 pub struct VotingStats {
     pub total_votes: u32,
+    pub abstain_votes: u32,
+    pub blank_votes: u32,
+    pub turnout: u32,
     pub total_parties: u32,
     pub total_voters: u32,
 }
 
+/// A call run by `execute` when its proposal wins. Defined here rather than in
+/// `governance` because `AdminAction` needs it even without the `proposals`
+/// feature.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invocation {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
 #[contract]
 pub struct VotingContract;
 
@@ -48,17 +197,274 @@ impl VotingContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Parties, &Vec::<Symbol>::new(&env));
         env.storage().instance().set(&DataKey::TotalVoters, &0u32);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
     }
 
     pub fn add_party(env: Env, party_name: Symbol) {
         let admin: Address = env.storage().instance().get(&DataKey::Admin)
             .expect("Contract not initialized");
         admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::insert_party(env, party_name);
+    }
+
+    pub fn add_voter(env: Env, voter: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::insert_voter(env, voter);
+    }
+
+    pub fn vote(env: Env, voter: Address, party_name: Symbol) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        let weight = Self::ballot_weight(&env, &voter);
+        Self::cast_vote(&env, &voter, vec![&env, party_name], weight);
+    }
+
+    pub fn get_vote_count(env: Env, party_name: Symbol) -> u32 {
+        Self::require_results_visible(&env);
+        Self::tally(&env, party_name)
+    }
+
+    pub fn get_parties(env: Env) -> Vec<Symbol> {
+        env.storage().instance()
+            .get(&DataKey::Parties)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_voter_status(env: Env, voter: Address) -> VoterStatus {
+        env.storage().instance()
+            .get(&DataKey::VoterStatus(voter))
+            .unwrap_or(VoterStatus::NotRegistered)
+    }
+
+    pub fn get_voting_stats(env: Env) -> VotingStats {
+        let parties: Vec<Symbol> = Self::get_parties(env.clone());
+        let mut total_votes = 0u32;
+
+        for party in parties.iter() {
+            total_votes += Self::tally(&env, party);
+        }
+
+        let total_voters: u32 = env.storage().instance()
+            .get(&DataKey::TotalVoters)
+            .unwrap_or(0);
+
+        let abstain_votes = Self::tally(&env, ABSTAIN);
+        let blank_votes = Self::tally(&env, NOTA);
+        let turnout = Self::turnout(&env);
+
+        let (total_votes, abstain_votes, blank_votes) = if Self::results_visible(&env) {
+            (total_votes, abstain_votes, blank_votes)
+        } else {
+            (0, 0, 0)
+        };
+
+        VotingStats {
+            total_votes,
+            abstain_votes,
+            blank_votes,
+            turnout,
+            total_parties: parties.len(),
+            total_voters,
+        }
+    }
+
+    pub fn get_all_results(env: Env) -> Map<Symbol, u32> {
+        Self::require_results_visible(&env);
+
+        let parties: Vec<Symbol> = Self::get_parties(env.clone());
+        let mut results = Map::new(&env);
+
+        for party in parties.iter() {
+            let votes = Self::tally(&env, party.clone());
+            results.set(party, votes);
+        }
+
+        results.set(ABSTAIN, Self::tally(&env, ABSTAIN));
+        results.set(NOTA, Self::tally(&env, NOTA));
+        results
+    }
+
+    pub fn set_nota_competes(env: Env, enabled: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_nota_competes(env, enabled);
+    }
+
+    pub fn get_nota_competes(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DataKey::NotaCompetes)
+            .unwrap_or(false)
+    }
+
+    pub fn set_hide_results(env: Env, enabled: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_hide_results(env, enabled);
+    }
+
+    pub fn get_hide_results(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DataKey::HideResults)
+            .unwrap_or(false)
+    }
+
+    pub fn set_voting_start(env: Env, start: Deadline) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_voting_start(env, start);
+    }
+
+    pub fn get_voting_start(env: Env) -> Option<Deadline> {
+        env.storage().instance().get(&DataKey::VotingStart)
+    }
+
+    pub fn get_phase(env: Env) -> Phase {
+        if let Some(start) = Self::get_voting_start(env.clone()) {
+            if !start.is_reached(&env) {
+                return Phase::Pending;
+            }
+        }
+
+        match Self::get_voting_deadline(env.clone()) {
+            Some(deadline) if deadline.has_passed(&env) => Phase::Closed,
+            _ => Phase::Open,
+        }
+    }
+
+    pub fn set_voting_deadline(env: Env, deadline: Deadline, reason: String) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_voting_deadline(env, deadline, reason);
+    }
+
+    pub fn get_voting_deadline(env: Env) -> Option<Deadline> {
+        env.storage().instance().get(&DataKey::VotingDeadline)
+    }
+
+    pub fn get_deadline_history(env: Env) -> Vec<DeadlineChange> {
+        env.storage().instance()
+            .get(&DataKey::DeadlineHistory)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Measured in seconds for timestamp deadlines and in ledgers for ledger deadlines.
+    pub fn set_max_deadline_extension(env: Env, max_extension: u64) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_max_deadline_extension(env, max_extension);
+    }
+
+    pub fn get_max_deadline_extension(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::MaxDeadlineExtension)
+    }
+
+    pub fn finalize(env: Env) -> FinalResult {
+        Self::require_not_paused(&env);
+
+        if Self::get_phase(env.clone()) != Phase::Closed {
+            panic!("Voting period has not ended");
+        }
+
+        if Self::is_finalized(env.clone()) {
+            panic!("Election already finalized");
+        }
+
+        if Self::tally_pending(&env) {
+            panic!("Tally not published");
+        }
+
+        let result = FinalResult {
+            election: env.current_contract_address(),
+            winner: Self::get_winner(env.clone()),
+            winners: Self::get_winners(env.clone()),
+            results: Self::get_all_results(env.clone()),
+            finalized_at: env.ledger().timestamp(),
+        };
+
+        env.storage().instance().set(&DataKey::Finalized, &result.finalized_at);
+        env.storage().instance().set(&DataKey::FinalResult, &result);
+        env.events().publish((symbol_short!("finalized"),), result.winner.clone());
+
+        let callback = Symbol::new(&env, "on_finalized");
+        for subscriber in Self::get_subscribers(env.clone()).iter() {
+            let notified = env.try_invoke_contract::<(), soroban_sdk::InvokeError>(
+                &subscriber,
+                &callback,
+                vec![&env, result.clone().into_val(&env)],
+            );
+            if !matches!(notified, Ok(Ok(()))) {
+                env.events().publish((symbol_short!("notify_ko"),), subscriber);
+            }
+        }
+
+        result
+    }
+
+    pub fn get_final_result(env: Env) -> Option<FinalResult> {
+        env.storage().instance().get(&DataKey::FinalResult)
+    }
+
+    /// Subscribers are notified from `finalize`; one that fails is reported in
+    /// a `notify_ko` event and does not block finalization.
+    pub fn add_subscriber(env: Env, subscriber: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::insert_subscriber(env, subscriber);
+    }
+
+    pub fn remove_subscriber(env: Env, subscriber: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::delete_subscriber(env, subscriber);
+    }
 
+    pub fn get_subscribers(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&DataKey::Subscribers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_finalized_at(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::Finalized)
+    }
+
+    fn insert_party(env: Env, party_name: Symbol) {
         let mut parties: Vec<Symbol> = env.storage().instance()
             .get(&DataKey::Parties)
             .unwrap_or_else(|| Vec::new(&env));
 
+        if party_name == ABSTAIN || party_name == NOTA {
+            panic!("Party name is reserved");
+        }
+
         if parties.contains(&party_name) {
             panic!("Party already registered");
         }
@@ -68,203 +474,360 @@ impl VotingContract {
         env.storage().instance().set(&DataKey::Votes(party_name), &0u32);
     }
 
-    pub fn add_voter(env: Env, voter: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin)
-            .expect("Contract not initialized");
-        admin.require_auth();
-
+    fn insert_voter(env: Env, voter: Address) {
         let current_status: VoterStatus = env.storage().instance()
             .get(&DataKey::VoterStatus(voter.clone()))
             .unwrap_or(VoterStatus::NotRegistered);
 
         match current_status {
-            VoterStatus::NotRegistered => {
-                env.storage().instance().set(&DataKey::VoterStatus(voter), &VoterStatus::Registered);
-                let total_voters: u32 = env.storage().instance()
-                    .get(&DataKey::TotalVoters)
-                    .unwrap_or(0);
-                env.storage().instance().set(&DataKey::TotalVoters, &(total_voters + 1));
-            }
+            VoterStatus::NotRegistered => Self::register_voter(&env, &voter),
             _ => panic!("Voter already registered"),
         }
     }
 
-    pub fn vote(env: Env, voter: Address, party_name: Symbol) {
-        voter.require_auth();
+    fn store_voting_start(env: Env, start: Deadline) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if start.is_reached(&env) {
+            panic!("Start must be in the future");
+        }
 
-        if let Some(deadline) = env.storage().instance().get::<DataKey, u64>(&DataKey::VotingDeadline) {
-            if env.ledger().timestamp() > deadline {
+        if let Some(deadline) = Self::get_voting_deadline(env.clone()) {
+            if deadline.extension_from(&start).is_none() {
+                panic!("Start must be before the deadline");
+            }
+        }
+
+        env.storage().instance().set(&DataKey::VotingStart, &start);
+    }
+
+    fn store_voting_deadline(env: Env, deadline: Deadline, reason: String) {
+        if deadline.is_reached(&env) {
+            panic!("Deadline must be in the future");
+        }
+
+        let previous = Self::get_voting_deadline(env.clone());
+        if let Some(previous) = &previous {
+            if previous.has_passed(&env) {
                 panic!("Voting period has ended");
             }
         }
 
-        let voter_status: VoterStatus = env.storage().instance()
-            .get(&DataKey::VoterStatus(voter.clone()))
-            .unwrap_or(VoterStatus::NotRegistered);
+        if let Some(start) = Self::get_voting_start(env.clone()) {
+            if deadline.extension_from(&start).is_none() {
+                panic!("Start must be before the deadline");
+            }
+        }
 
-        match voter_status {
-            VoterStatus::Registered => {},
-            VoterStatus::NotRegistered => panic!("Voter not registered"),
-            VoterStatus::Voted => panic!("Voter already voted"),
-            VoterStatus::Delegated(_) => panic!("Voter has delegated their vote"),
+        if Self::voting_started(&env) {
+            let previous = match &previous {
+                Some(previous) if deadline.extension_from(previous).is_some() => previous.clone(),
+                _ => panic!("Deadline can only be extended once voting has started"),
+            };
+
+            // Extensions are capped in total, measured from the deadline that
+            // was in force when voting started.
+            let base: Deadline = env.storage().instance()
+                .get(&DataKey::BaseDeadline)
+                .unwrap_or(previous);
+            env.storage().instance().set(&DataKey::BaseDeadline, &base);
+
+            if let Some(max_extension) = Self::get_max_deadline_extension(env.clone()) {
+                if deadline.extension_from(&base).unwrap_or(0) > max_extension {
+                    panic!("Deadline extension exceeds maximum");
+                }
+            }
+        }
+
+        let mut history: Vec<DeadlineChange> = Self::get_deadline_history(env.clone());
+        history.push_back(DeadlineChange {
+            previous: match previous {
+                Some(previous) => PreviousDeadline::Set(previous),
+                None => PreviousDeadline::Unset,
+            },
+            deadline: deadline.clone(),
+            reason,
+            changed_at: env.ledger().timestamp(),
+        });
+
+        env.storage().instance().set(&DataKey::DeadlineHistory, &history);
+        env.storage().instance().set(&DataKey::VotingDeadline, &deadline);
+    }
+
+    fn store_nota_competes(env: Env, enabled: bool) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&DataKey::NotaCompetes, &enabled);
+    }
+
+    fn store_hide_results(env: Env, enabled: bool) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        // Results are revealed at the deadline, so one must exist.
+        if enabled && Self::get_voting_deadline(env.clone()).is_none() {
+            panic!("Hidden results require a deadline");
+        }
+
+        env.storage().instance().set(&DataKey::HideResults, &enabled);
+    }
+
+    fn store_max_deadline_extension(env: Env, max_extension: u64) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        env.storage().instance().set(&DataKey::MaxDeadlineExtension, &max_extension);
+    }
+
+    fn insert_subscriber(env: Env, subscriber: Address) {
+        let mut subscribers = Self::get_subscribers(env.clone());
+        if subscribers.contains(&subscriber) {
+            panic!("Subscriber already registered");
+        }
+
+        if subscribers.len() >= MAX_SUBSCRIBERS {
+            panic!("Too many subscribers");
+        }
+
+        subscribers.push_back(subscriber);
+        env.storage().instance().set(&DataKey::Subscribers, &subscribers);
+    }
+
+    fn delete_subscriber(env: Env, subscriber: Address) {
+        let mut subscribers = Self::get_subscribers(env.clone());
+        let index = subscribers.first_index_of(&subscriber).expect("Subscriber not found");
+        subscribers.remove(index);
+        env.storage().instance().set(&DataKey::Subscribers, &subscribers);
+    }
+
+    fn cast_vote(env: &Env, voter: &Address, choices: Vec<Symbol>, weight: u32) {
+        if Self::encrypted_ballots(env) {
+            panic!("Election uses encrypted ballots");
         }
 
+        let (represented, represented_by) = Self::open_ballot(env, voter);
+
         let parties: Vec<Symbol> = env.storage().instance()
             .get(&DataKey::Parties)
             .expect("No parties registered");
 
-        if !parties.contains(&party_name) {
-            panic!("Party not found");
+        for party_name in choices.iter() {
+            if party_name != ABSTAIN && party_name != NOTA && !parties.contains(&party_name) {
+                panic!("Party not found");
+            }
         }
 
-        let delegated_votes: u32 = env.storage().instance()
-            .get(&DataKey::DelegatedVotes(voter.clone()))
-            .unwrap_or(0);
+        let weights = Self::represented_weights(env, &represented);
+        let total_voting_power = Self::voting_power(env, voter, weight, &weights);
+
+        if let Some(caster) = represented_by {
+            let mut represented_power: u32 = env.storage().instance()
+                .get(&DelegationKey::RepresentedWeight(voter.clone()))
+                .unwrap_or(1);
+            for delegator in represented.iter() {
+                represented_power += env.storage().instance()
+                    .get::<DelegationKey, u32>(&DelegationKey::RepresentedWeight(delegator))
+                    .unwrap_or(1);
+            }
+            for cast_party in Self::ballot_choices(env, &caster).iter() {
+                let cast_votes: u32 = env.storage().instance()
+                    .get(&DataKey::Votes(cast_party.clone()))
+                    .unwrap_or(0);
+                env.storage().instance().set(&DataKey::Votes(cast_party), &(cast_votes - represented_power));
+            }
+            env.storage().instance().set(&DataKey::Turnout, &(Self::turnout(env) - represented_power));
+            env.storage().instance().remove(&DelegationKey::RepresentedBy(voter.clone()));
+            env.storage().instance().remove(&DelegationKey::RepresentedWeight(voter.clone()));
+        }
 
-        let total_voting_power = 1 + delegated_votes;
+        for party_name in choices.iter() {
+            let current_votes: u32 = env.storage().instance()
+                .get(&DataKey::Votes(party_name.clone()))
+                .unwrap_or(0);
+            env.storage().instance().set(&DataKey::Votes(party_name), &(current_votes + total_voting_power));
+        }
+        env.storage().instance().set(&DataKey::Turnout, &(Self::turnout(env) + total_voting_power));
 
-        let current_votes: u32 = env.storage().instance()
-            .get(&DataKey::Votes(party_name.clone()))
-            .unwrap_or(0);
+        if choices.len() == 1 {
+            env.storage().instance().set(&DataKey::Ballot(voter.clone()), &choices.get_unchecked(0));
+        } else {
+            env.storage().instance().set(&SeatKey::BlockBallot(voter.clone()), &choices);
+        }
+        Self::close_ballot(env, voter, &represented, &weights);
+    }
 
-        env.storage().instance().set(&DataKey::Votes(party_name), &(current_votes + total_voting_power));
-        env.storage().instance().set(&DataKey::VoterStatus(voter), &VoterStatus::Voted);
+    fn voting_power(env: &Env, voter: &Address, weight: u32, represented_weights: &Vec<u32>) -> u32 {
+        weight * Self::oracle_weight(env, voter) + represented_weights.iter().sum::<u32>()
+    }
+
+    fn register_voter(env: &Env, voter: &Address) {
+        env.storage().instance().set(&DataKey::VoterStatus(voter.clone()), &VoterStatus::Registered);
+        let total_voters: u32 = env.storage().instance()
+            .get(&DataKey::TotalVoters)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::TotalVoters, &(total_voters + 1));
     }
 
-    pub fn delegate(env: Env, delegator: Address, delegate_to: Address) {
-        delegator.require_auth();
+    fn open_ballot(env: &Env, voter: &Address) -> (Vec<Address>, Option<Address>) {
+        if Self::anonymous_ballots(env) {
+            panic!("Election uses anonymous ballots");
+        }
 
-        if delegator == delegate_to {
-            panic!("Cannot delegate to yourself");
+        match Self::get_phase(env.clone()) {
+            Phase::Pending => panic!("Voting has not started"),
+            Phase::Closed => panic!("Voting period has ended"),
+            Phase::Open => {},
         }
 
-        Self::check_circular_delegation(&env, &delegator, &delegate_to);
+        Self::register_if_eligible(env, voter, true);
 
-        let delegator_status: VoterStatus = env.storage().instance()
-            .get(&DataKey::VoterStatus(delegator.clone()))
-            .unwrap_or(VoterStatus::NotRegistered);
+        if !Self::oracle_allows(env, voter) {
+            panic!("Voter not eligible");
+        }
 
-        let delegate_status: VoterStatus = env.storage().instance()
-            .get(&DataKey::VoterStatus(delegate_to.clone()))
+        let voter_status: VoterStatus = env.storage().instance()
+            .get(&DataKey::VoterStatus(voter.clone()))
             .unwrap_or(VoterStatus::NotRegistered);
 
-        match delegator_status {
-            VoterStatus::Registered => {},
-            VoterStatus::NotRegistered => panic!("Delegator not registered"),
-            VoterStatus::Voted => panic!("Delegator already voted"),
-            VoterStatus::Delegated(_) => panic!("Delegator already delegated"),
+        match voter_status {
+            VoterStatus::Registered | VoterStatus::Delegated(_) => {},
+            VoterStatus::NotRegistered => panic!("Voter not registered"),
+            VoterStatus::Voted => panic!("Voter already voted"),
         }
 
-        match delegate_status {
-            VoterStatus::Registered => {},
-            VoterStatus::NotRegistered => panic!("Delegate not registered"),
-            VoterStatus::Voted => panic!("Cannot delegate to someone who already voted"),
-            VoterStatus::Delegated(_) => panic!("Cannot delegate to someone who delegated"),
+        let category = Self::get_category(env.clone());
+        let represented_by: Option<Address> = env.storage().instance()
+            .get(&DelegationKey::RepresentedBy(voter.clone()));
+        let delegated = represented_by.is_some() || Self::active_delegate(env, voter, &category).is_some();
+
+        if delegated && !Self::get_delegate_override(env.clone()) {
+            panic!("Voter has delegated their vote");
         }
 
-        let current_delegated: u32 = env.storage().instance()
-            .get(&DataKey::DelegatedVotes(delegate_to.clone()))
-            .unwrap_or(0);
+        (Self::represented_voters(env, voter, &category, &represented_by), represented_by)
+    }
+
+    /// Records who carried each delegated vote, and with what weight, so an
+    /// overriding delegator can later take exactly that weight back.
+    fn close_ballot(env: &Env, voter: &Address, represented: &Vec<Address>, weights: &Vec<u32>) {
+        for (delegator, weight) in represented.iter().zip(weights.iter()) {
+            env.storage().instance().set(&DelegationKey::RepresentedBy(delegator.clone()), voter);
+            env.storage().instance().set(&DelegationKey::RepresentedWeight(delegator), &weight);
+        }
 
-        env.storage().instance().set(&DataKey::DelegatedVotes(delegate_to.clone()), &(current_delegated + 1));
-        env.storage().instance().set(&DataKey::VoterStatus(delegator), &VoterStatus::Delegated(delegate_to));
+        env.storage().instance().set(&DataKey::VotingStarted, &true);
+        env.storage().instance().set(&DataKey::VoterStatus(voter.clone()), &VoterStatus::Voted);
     }
 
-    pub fn get_vote_count(env: Env, party_name: Symbol) -> u32 {
+    /// Total weight of the ballots cast, counted once per ballot however many
+    /// parties a block ballot backs.
+    fn turnout(env: &Env) -> u32 {
         env.storage().instance()
-            .get(&DataKey::Votes(party_name))
+            .get(&DataKey::Turnout)
             .unwrap_or(0)
     }
 
-    pub fn get_parties(env: Env) -> Vec<Symbol> {
+    fn tally(env: &Env, party_name: Symbol) -> u32 {
         env.storage().instance()
-            .get(&DataKey::Parties)
-            .unwrap_or_else(|| Vec::new(&env))
+            .get(&DataKey::Votes(party_name))
+            .unwrap_or(0)
     }
 
-    pub fn get_voter_status(env: Env, voter: Address) -> VoterStatus {
-        env.storage().instance()
-            .get(&DataKey::VoterStatus(voter))
-            .unwrap_or(VoterStatus::NotRegistered)
+    fn results_visible(env: &Env) -> bool {
+        !Self::get_hide_results(env.clone()) || Self::get_phase(env.clone()) == Phase::Closed
     }
 
-    pub fn get_voting_stats(env: Env) -> VotingStats {
-        let parties: Vec<Symbol> = Self::get_parties(env.clone());
-        let mut total_votes = 0u32;
-
-        for party in parties.iter() {
-            total_votes += Self::get_vote_count(env.clone(), party);
+    fn require_results_visible(env: &Env) {
+        if !Self::results_visible(env) {
+            panic!("Results are hidden until the deadline");
         }
+    }
 
-        let total_voters: u32 = env.storage().instance()
-            .get(&DataKey::TotalVoters)
-            .unwrap_or(0);
+    fn voting_started(env: &Env) -> bool {
+        let ballot_cast: bool = env.storage().instance()
+            .get(&DataKey::VotingStarted)
+            .unwrap_or(false);
 
-        VotingStats {
-            total_votes,
-            total_parties: parties.len(),
-            total_voters,
-        }
+        ballot_cast || Self::get_voting_start(env.clone()).is_some_and(|start| start.is_reached(env))
     }
 
-    pub fn get_all_results(env: Env) -> Map<Symbol, u32> {
-        let parties: Vec<Symbol> = Self::get_parties(env.clone());
-        let mut results = Map::new(&env);
+}
 
-        for party in parties.iter() {
-            let votes = Self::get_vote_count(env.clone(), party.clone());
-            results.set(party, votes);
-        }
+#[contractimpl]
+impl VotingReader for VotingContract {
+    fn reader_version(_env: Env) -> u32 {
+        VOTING_READER_VERSION
+    }
 
-        results
+    fn get_result(env: Env, party_name: Symbol) -> u32 {
+        Self::get_vote_count(env, party_name)
     }
 
-    pub fn set_voting_deadline(env: Env, deadline: u64) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin)
-            .expect("Contract not initialized");
-        admin.require_auth();
+    fn is_finalized(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::Finalized)
+    }
 
-        if deadline <= env.ledger().timestamp() {
-            panic!("Deadline must be in the future");
+    fn get_winner(env: Env) -> Option<Symbol> {
+        Self::require_results_visible(&env);
+        let mut winner: Option<Symbol> = None;
+        let mut winning_votes = 0u32;
+
+        for party in Self::get_parties(env.clone()).iter() {
+            let votes = Self::tally(&env, party.clone());
+            if votes > winning_votes {
+                winner = Some(party);
+                winning_votes = votes;
+            }
         }
 
-        env.storage().instance().set(&DataKey::VotingDeadline, &deadline);
-    }
+        if Self::get_nota_competes(env.clone()) && Self::tally(&env, NOTA) > winning_votes {
+            return Some(NOTA);
+        }
 
-    pub fn get_voting_deadline(env: Env) -> Option<u64> {
-        env.storage().instance().get(&DataKey::VotingDeadline)
+        winner
     }
 
-    fn check_circular_delegation(env: &Env, delegator: &Address, delegate_to: &Address) {
-        let mut current = delegate_to.clone();
-        let mut visited = Vec::new(&env);
-        
-        loop {
-            if visited.contains(&current) {
-                panic!("Circular delegation detected");
-            }
-            
-            visited.push_back(current.clone());
-            
-            if current == *delegator {
-                panic!("Circular delegation detected");
-            }
-            
-            let status: VoterStatus = env.storage().instance()
-                .get(&DataKey::VoterStatus(current.clone()))
-                .unwrap_or(VoterStatus::NotRegistered);
-            
-            match status {
-                VoterStatus::Delegated(next_delegate) => {
-                    current = next_delegate;
-                },
-                _ => break,
+    /// Up to `seats` parties with at least one vote, most votes first; equal
+    /// tallies are ordered by registration, earliest first. No seats are
+    /// filled when NOTA wins.
+    fn get_winners(env: Env) -> Vec<Symbol> {
+        Self::require_results_visible(&env);
+        let parties = Self::get_parties(env.clone());
+        let mut winners: Vec<Symbol> = Vec::new(&env);
+
+        if Self::get_winner(env.clone()) == Some(NOTA) {
+            return winners;
+        }
+
+        while winners.len() < Self::get_seats(env.clone()) {
+            let mut next: Option<Symbol> = None;
+            let mut next_votes = 0u32;
+
+            for party in parties.iter() {
+                let votes = Self::tally(&env, party.clone());
+                if votes > next_votes && !winners.contains(&party) {
+                    next = Some(party);
+                    next_votes = votes;
+                }
             }
-            
-            if visited.len() > 100 {
-                panic!("Delegation chain too long");
+
+            match next {
+                Some(party) => winners.push_back(party),
+                None => break,
             }
         }
+
+        winners
+    }
+
+    fn has_voted(env: Env, voter: Address) -> bool {
+        Self::get_voter_status(env, voter) == VoterStatus::Voted
     }
 }
 
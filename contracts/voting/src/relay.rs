@@ -0,0 +1,237 @@
+use soroban_sdk::{contractimpl, contracttype, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    delegation::DelegationKey, DataKey, Phase, VoterStatus, VotingContract, VotingContractArgs, VotingContractClient,
+    ABSTAIN, NOTA,
+};
+
+const BALLOT_DOMAIN: &[u8] = b"stellar-vote:ballot:v1";
+
+/// Order of the ed25519 base point, little-endian.
+const ED25519_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+#[contracttype(export = false)]
+pub enum RelayKey {
+    VoterKey(BytesN<32>),
+    SigningKey(Address),
+    Nonce(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedBallot {
+    pub voter_pubkey: BytesN<32>,
+    pub party: Symbol,
+    pub nonce: u64,
+    pub expiry: u64,
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BallotResult {
+    Accepted,
+    UnknownKey,
+    Expired,
+    InvalidNonce,
+    NotRegistered,
+    AlreadyVoted,
+    Delegated,
+    PartyNotFound,
+    NotEligible,
+    InvalidSignature,
+    NotOpen,
+    WrongBallotMode,
+}
+
+#[contractimpl]
+impl VotingContract {
+    /// Registers the ed25519 key relayers present on the voter's behalf,
+    /// replacing any key the voter registered before. Keys are frozen once
+    /// voting starts.
+    pub fn set_voter_key(env: Env, voter: Address, public_key: BytesN<32>) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if env.storage().instance().has(&RelayKey::VoterKey(public_key.clone())) {
+            panic!("Signing key already registered");
+        }
+
+        if let Some(previous) = Self::get_voter_key(env.clone(), voter.clone()) {
+            env.storage().instance().remove(&RelayKey::VoterKey(previous));
+        }
+
+        env.storage().instance().set(&RelayKey::VoterKey(public_key.clone()), &voter);
+        env.storage().instance().set(&RelayKey::SigningKey(voter), &public_key);
+    }
+
+    pub fn revoke_voter_key(env: Env, voter: Address) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        let public_key = Self::get_voter_key(env.clone(), voter.clone())
+            .expect("No signing key registered");
+        env.storage().instance().remove(&RelayKey::VoterKey(public_key));
+        env.storage().instance().remove(&RelayKey::SigningKey(voter));
+    }
+
+    pub fn get_voter_key(env: Env, voter: Address) -> Option<BytesN<32>> {
+        env.storage().instance().get(&RelayKey::SigningKey(voter))
+    }
+
+    pub fn get_key_voter(env: Env, public_key: BytesN<32>) -> Option<Address> {
+        env.storage().instance().get(&RelayKey::VoterKey(public_key))
+    }
+
+    pub fn get_nonce(env: Env, voter: Address) -> u64 {
+        env.storage().instance()
+            .get(&RelayKey::Nonce(voter))
+            .unwrap_or(0)
+    }
+
+    pub fn get_ballot_message(env: Env, voter_pubkey: BytesN<32>, party_name: Symbol, nonce: u64, expiry: u64) -> Bytes {
+        let mut message = Bytes::from_slice(&env, BALLOT_DOMAIN);
+        message.append(&(env.current_contract_address(), voter_pubkey, party_name, nonce, expiry).to_xdr(&env));
+        message
+    }
+
+    pub fn vote_by_signature(env: Env, voter_pubkey: BytesN<32>, party_name: Symbol, nonce: u64, expiry: u64, signature: BytesN<64>) {
+        Self::require_not_paused(&env);
+        Self::require_unbonded(&env);
+
+        let voter = Self::get_key_voter(env.clone(), voter_pubkey.clone())
+            .expect("Signing key not registered");
+
+        // Relayed ballots carry no authorization for the eligibility deposit.
+        Self::register_if_eligible(&env, &voter, false);
+        if Self::get_voter_status(env.clone(), voter.clone()) == VoterStatus::NotRegistered {
+            panic!("Voter not registered");
+        }
+
+        if env.ledger().timestamp() > expiry {
+            panic!("Signature expired");
+        }
+
+        if nonce != Self::get_nonce(env.clone(), voter.clone()) {
+            panic!("Invalid nonce");
+        }
+
+        let message = Self::get_ballot_message(env.clone(), voter_pubkey.clone(), party_name.clone(), nonce, expiry);
+        env.crypto().ed25519_verify(&voter_pubkey, &message, &signature);
+
+        env.storage().instance().set(&RelayKey::Nonce(voter.clone()), &(nonce + 1));
+        Self::cast_vote(&env, &voter, vec![&env, party_name], 1);
+    }
+
+    /// Every ballot is checked on its own and rejected ones are reported
+    /// instead of aborting the batch. Malformed signatures are reported as
+    /// `InvalidSignature`; the host traps on a well-formed signature that does
+    /// not verify, which aborts the batch, so relayers should check signatures
+    /// before submitting them.
+    pub fn submit_ballots(env: Env, ballots: Vec<SignedBallot>) -> Vec<BallotResult> {
+        Self::require_not_paused(&env);
+        Self::require_unbonded(&env);
+
+        let mut results = Vec::new(&env);
+        for ballot in ballots.iter() {
+            let voter = match Self::get_key_voter(env.clone(), ballot.voter_pubkey.clone()) {
+                Some(voter) => voter,
+                None => {
+                    results.push_back(BallotResult::UnknownKey);
+                    continue;
+                }
+            };
+
+            let rejection = if env.ledger().timestamp() > ballot.expiry {
+                Some(BallotResult::Expired)
+            } else if ballot.nonce != Self::get_nonce(env.clone(), voter.clone()) {
+                Some(BallotResult::InvalidNonce)
+            } else {
+                Self::ballot_rejection(&env, &voter, &ballot.party)
+            };
+            if let Some(result) = rejection {
+                results.push_back(result);
+                continue;
+            }
+
+            if !Self::signature_is_canonical(&ballot.signature) {
+                results.push_back(BallotResult::InvalidSignature);
+                continue;
+            }
+
+            let message = Self::get_ballot_message(env.clone(), ballot.voter_pubkey.clone(), ballot.party.clone(), ballot.nonce, ballot.expiry);
+            env.crypto().ed25519_verify(&ballot.voter_pubkey, &message, &ballot.signature);
+
+            env.storage().instance().set(&RelayKey::Nonce(voter.clone()), &(ballot.nonce + 1));
+            Self::cast_vote(&env, &voter, vec![&env, ballot.party], 1);
+            results.push_back(BallotResult::Accepted);
+        }
+
+        results
+    }
+
+    /// The host rejects ed25519 signatures whose scalar half is not reduced
+    /// modulo the group order; catching those here keeps them from trapping.
+    fn signature_is_canonical(signature: &BytesN<64>) -> bool {
+        let scalar = &signature.to_array()[32..];
+        for i in (0..32).rev() {
+            if scalar[i] != ED25519_ORDER[i] {
+                return scalar[i] < ED25519_ORDER[i];
+            }
+        }
+        false
+    }
+
+    fn ballot_rejection(env: &Env, voter: &Address, party_name: &Symbol) -> Option<BallotResult> {
+        if Self::get_phase(env.clone()) != Phase::Open {
+            return Some(BallotResult::NotOpen);
+        }
+
+        if Self::encrypted_ballots(env) || Self::anonymous_ballots(env) {
+            return Some(BallotResult::WrongBallotMode);
+        }
+
+        Self::register_if_eligible(env, voter, false);
+
+        if !Self::oracle_allows(env, voter) {
+            return Some(BallotResult::NotEligible);
+        }
+
+        let voter_status: VoterStatus = env.storage().instance()
+            .get(&DataKey::VoterStatus(voter.clone()))
+            .unwrap_or(VoterStatus::NotRegistered);
+
+        match voter_status {
+            VoterStatus::NotRegistered => return Some(BallotResult::NotRegistered),
+            VoterStatus::Voted => return Some(BallotResult::AlreadyVoted),
+            VoterStatus::Registered | VoterStatus::Delegated(_) => {},
+        }
+
+        let category = Self::get_category(env.clone());
+        let delegated = env.storage().instance().has(&DelegationKey::RepresentedBy(voter.clone()))
+            || Self::active_delegate(env, voter, &category).is_some();
+        if delegated && !Self::get_delegate_override(env.clone()) {
+            return Some(BallotResult::Delegated);
+        }
+
+        let parties: Vec<Symbol> = env.storage().instance()
+            .get(&DataKey::Parties)
+            .unwrap_or(Vec::new(env));
+        if *party_name != ABSTAIN && *party_name != NOTA && !parties.contains(party_name) {
+            return Some(BallotResult::PartyNotFound);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,164 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, Env};
+use voting_interface::VotingReader;
+
+use crate::{delegation::DelegationKey, DataKey, VoterStatus, VotingContract, VotingContractArgs, VotingContractClient};
+
+#[contracttype(export = false)]
+pub enum RewardKey {
+    Pool,
+    Claimed(Address),
+    ClaimWindow,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardPool {
+    pub token: Address,
+    pub amount_per_vote: i128,
+    pub balance: i128,
+}
+
+#[contractimpl]
+impl VotingContract {
+    /// Voters can claim for `claim_window` seconds after finalization; after
+    /// that the admin may reclaim what is left with `reclaim_rewards`.
+    pub fn set_reward_pool(env: Env, token: Address, amount_per_vote: i128, claim_window: u64) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_reward_pool(env, token, amount_per_vote, claim_window);
+    }
+
+    pub fn get_reward_claim_window(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&RewardKey::ClaimWindow)
+            .unwrap_or(0)
+    }
+
+    pub fn get_reward_pool(env: Env) -> Option<RewardPool> {
+        env.storage().instance().get(&RewardKey::Pool)
+    }
+
+    pub fn fund_rewards(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        Self::require_not_paused(&env);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let mut pool = Self::get_reward_pool(env.clone()).expect("Reward pool not configured");
+        token::Client::new(&env, &pool.token).transfer(&funder, &env.current_contract_address(), &amount);
+
+        pool.balance += amount;
+        env.storage().instance().set(&RewardKey::Pool, &pool);
+    }
+
+    /// Pays the per-vote amount, or whatever is left once the pool runs low.
+    pub fn claim_reward(env: Env, voter: Address) -> i128 {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        let mut pool = Self::get_reward_pool(env.clone()).expect("Reward pool not configured");
+
+        if !Self::is_finalized(env.clone()) {
+            panic!("Election not finalized");
+        }
+
+        if Self::reward_claims_closed(&env) {
+            panic!("Claim window has closed");
+        }
+
+        if Self::has_claimed_reward(env.clone(), voter.clone()) {
+            panic!("Reward already claimed");
+        }
+
+        let participated = Self::get_voter_status(env.clone(), voter.clone()) == VoterStatus::Voted
+            || env.storage().instance().has(&DelegationKey::RepresentedBy(voter.clone()));
+        if !participated {
+            panic!("Voter did not participate");
+        }
+
+        if pool.balance == 0 {
+            panic!("Reward pool exhausted");
+        }
+
+        let payout = pool.amount_per_vote.min(pool.balance);
+        pool.balance -= payout;
+        env.storage().instance().set(&RewardKey::Pool, &pool);
+        env.storage().instance().set(&RewardKey::Claimed(voter.clone()), &true);
+
+        token::Client::new(&env, &pool.token).transfer(&env.current_contract_address(), &voter, &payout);
+        payout
+    }
+
+    pub fn has_claimed_reward(env: Env, voter: Address) -> bool {
+        env.storage().instance()
+            .get(&RewardKey::Claimed(voter))
+            .unwrap_or(false)
+    }
+
+    /// Returns the unclaimed rewards to the admin once the claim window has
+    /// closed.
+    pub fn reclaim_rewards(env: Env) -> i128 {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::release_rewards(env)
+    }
+
+    fn reward_claims_closed(env: &Env) -> bool {
+        Self::get_finalized_at(env.clone())
+            .is_some_and(|finalized_at| env.ledger().timestamp() > finalized_at.saturating_add(Self::get_reward_claim_window(env.clone())))
+    }
+
+    pub(crate) fn store_reward_pool(env: Env, token: Address, amount_per_vote: i128, claim_window: u64) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if amount_per_vote <= 0 {
+            panic!("Reward amount must be positive");
+        }
+
+        let balance = match Self::get_reward_pool(env.clone()) {
+            Some(pool) if pool.balance > 0 && pool.token != token => panic!("Reward pool already funded"),
+            Some(pool) => pool.balance,
+            None => 0,
+        };
+
+        env.storage().instance().set(&RewardKey::Pool, &RewardPool {
+            token,
+            amount_per_vote,
+            balance,
+        });
+        env.storage().instance().set(&RewardKey::ClaimWindow, &claim_window);
+    }
+
+    pub(crate) fn release_rewards(env: Env) -> i128 {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        let mut pool = Self::get_reward_pool(env.clone()).expect("Reward pool not configured");
+
+        if !Self::is_finalized(env.clone()) {
+            panic!("Election not finalized");
+        }
+
+        if !Self::reward_claims_closed(&env) {
+            panic!("Claim window is still open");
+        }
+
+        let amount = pool.balance;
+        pool.balance = 0;
+        env.storage().instance().set(&RewardKey::Pool, &pool);
+
+        if amount > 0 {
+            token::Client::new(&env, &pool.token).transfer(&env.current_contract_address(), &admin, &amount);
+        }
+        amount
+    }
+}
@@ -0,0 +1,85 @@
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, Symbol, Vec};
+
+use crate::{DataKey, VotingContract, VotingContractArgs, VotingContractClient, ABSTAIN, NOTA};
+
+#[contracttype(export = false)]
+pub enum SeatKey {
+    SeatConfig,
+    BlockBallot(Address),
+}
+
+#[contractimpl]
+impl VotingContract {
+    /// Block voting: one ballot backs up to `seats` distinct parties, each
+    /// receiving the voter's full weight.
+    pub fn vote_block(env: Env, voter: Address, party_names: Vec<Symbol>) {
+        voter.require_auth();
+        Self::require_not_paused(&env);
+
+        if !Self::get_block_voting(env.clone()) {
+            panic!("Block voting is not enabled");
+        }
+
+        if party_names.is_empty() || party_names.len() > Self::get_seats(env.clone()) {
+            panic!("Invalid number of candidates");
+        }
+
+        for (i, party_name) in party_names.iter().enumerate() {
+            if party_name == ABSTAIN || party_name == NOTA {
+                panic!("Party name is reserved");
+            }
+            if party_names.first_index_of(&party_name) != Some(i as u32) {
+                panic!("Duplicate candidate");
+            }
+        }
+
+        let weight = Self::ballot_weight(&env, &voter);
+        Self::cast_vote(&env, &voter, party_names, weight);
+    }
+
+    pub fn set_seats(env: Env, seats: u32, block_voting: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        Self::require_not_paused(&env);
+        Self::require_direct_admin(&env);
+        Self::store_seats(env, seats, block_voting);
+    }
+
+    pub fn get_seats(env: Env) -> u32 {
+        Self::seat_config(&env).0
+    }
+
+    pub fn get_block_voting(env: Env) -> bool {
+        Self::seat_config(&env).1
+    }
+
+    pub(crate) fn store_seats(env: Env, seats: u32, block_voting: bool) {
+        if Self::voting_started(&env) {
+            panic!("Voting has already started");
+        }
+
+        if seats == 0 {
+            panic!("Seats must be positive");
+        }
+
+        env.storage().instance().set(&SeatKey::SeatConfig, &(seats, block_voting));
+    }
+
+    fn seat_config(env: &Env) -> (u32, bool) {
+        env.storage().instance()
+            .get(&SeatKey::SeatConfig)
+            .unwrap_or((1, false))
+    }
+
+    pub(crate) fn ballot_choices(env: &Env, voter: &Address) -> Vec<Symbol> {
+        if let Some(choices) = env.storage().instance().get(&SeatKey::BlockBallot(voter.clone())) {
+            return choices;
+        }
+
+        let party_name: Symbol = env.storage().instance()
+            .get(&DataKey::Ballot(voter.clone()))
+            .expect("Delegate ballot not found");
+        vec![env, party_name]
+    }
+}
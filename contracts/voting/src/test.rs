@@ -1,7 +1,13 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{symbol_short, testutils::{Address as _, Ledger}, Address, Env};
+use crate::encrypted::G1_IDENTITY;
+use soroban_sdk::{
+    crypto::bls12_381::{Fr, G1Affine},
+    symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, U256,
+};
 
 fn create_test_env() -> (Env, Address, Address, Address, Address) {
     let env = Env::default();
@@ -198,8 +204,8 @@ fn test_delegation() {
     client.add_voter(&voter2);
     client.add_voter(&voter3);
 
-    client.delegate(&voter1, &voter2);
-    client.delegate(&voter3, &voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter3, &voter2, &None, &None);
     client.vote(&voter2, &party_a);
 
     assert_eq!(client.get_vote_count(&party_a), 3);
@@ -219,7 +225,7 @@ fn test_self_delegation() {
     client.initialize(&admin);
     client.add_voter(&voter1);
 
-    client.delegate(&voter1, &voter1);
+    client.delegate(&voter1, &voter1, &None, &None);
 }
 
 #[test]
@@ -233,7 +239,7 @@ fn test_unregistered_delegator() {
     client.initialize(&admin);
     client.add_voter(&voter2);
 
-    client.delegate(&voter1, &voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
 }
 
 #[test]
@@ -247,7 +253,7 @@ fn test_delegate_to_unregistered() {
     client.initialize(&admin);
     client.add_voter(&voter1);
 
-    client.delegate(&voter1, &voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
 }
 
 #[test]
@@ -265,7 +271,7 @@ fn test_delegated_voter_cannot_vote() {
     client.add_voter(&voter1);
     client.add_voter(&voter2);
 
-    client.delegate(&voter1, &voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
     client.vote(&voter1, &party_a);
 }
 
@@ -341,9 +347,9 @@ fn test_voting_deadline() {
     client.add_voter(&voter1);
 
     let future_deadline = env.ledger().timestamp() + 1000;
-    client.set_voting_deadline(&future_deadline);
+    client.set_voting_deadline(&Deadline::Timestamp(future_deadline), &String::from_str(&env, "Initial deadline"));
 
-    assert_eq!(client.get_voting_deadline(), Some(future_deadline));
+    assert_eq!(client.get_voting_deadline(), Some(Deadline::Timestamp(future_deadline)));
 
     client.vote(&voter1, &party_a);
     assert_eq!(client.get_vote_count(&party_a), 1);
@@ -360,7 +366,7 @@ fn test_past_deadline() {
     client.initialize(&admin);
     
     let past_deadline = env.ledger().timestamp().saturating_sub(1);
-    client.set_voting_deadline(&past_deadline);
+    client.set_voting_deadline(&Deadline::Timestamp(past_deadline), &String::from_str(&env, "Initial deadline"));
 }
 
 #[test]
@@ -378,7 +384,7 @@ fn test_voting_after_deadline() {
     client.add_voter(&voter1);
 
     let deadline = env.ledger().timestamp() + 100;
-    client.set_voting_deadline(&deadline);
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
 
     env.ledger().with_mut(|li| li.timestamp = deadline + 1);
 
@@ -398,9 +404,9 @@ fn test_circular_delegation() {
     client.add_voter(&voter2);
     client.add_voter(&voter3);
 
-    client.delegate(&voter1, &voter2);
-    client.delegate(&voter2, &voter3);
-    client.delegate(&voter3, &voter1);
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter2, &voter3, &None, &None);
+    client.delegate(&voter3, &voter1, &None, &None);
 }
 
 #[test]
@@ -423,4 +429,2993 @@ fn test_total_voters_count() {
     client.add_voter(&voter3);
     let stats_three = client.get_voting_stats();
     assert_eq!(stats_three.total_voters, 3);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_get_delegators() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    assert_eq!(client.get_delegators(&voter2, &0, &10).len(), 0);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter3, &voter2, &None, &None);
+
+    let delegators = client.get_delegators(&voter2, &0, &10);
+    assert_eq!(delegators.len(), 2);
+    assert_eq!(delegators.get(0).unwrap(), voter1);
+    assert_eq!(delegators.get(1).unwrap(), voter3);
+
+    let page = client.get_delegators(&voter2, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap(), voter3);
+
+    assert_eq!(client.get_delegators(&voter2, &5, &10).len(), 0);
+}
+
+#[test]
+fn test_delegation_chain() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter2, &voter3, &None, &None);
+
+    let chain = client.get_delegation_chain(&voter1);
+    assert_eq!(chain.len(), 2);
+    assert_eq!(chain.get(0).unwrap(), voter2);
+    assert_eq!(chain.get(1).unwrap(), voter3);
+    assert_eq!(client.get_delegation_chain(&voter3).len(), 0);
+
+    assert_eq!(client.get_delegated_power(&voter2), 1);
+    assert_eq!(client.get_delegated_power(&voter3), 2);
+
+    client.vote(&voter3, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 3);
+}
+
+#[test]
+#[should_panic(expected = "Cannot delegate to someone who already voted")]
+fn test_delegate_into_chain_that_voted() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.delegate(&voter2, &voter3, &None, &None);
+    client.vote(&voter3, &party_a);
+    client.delegate(&voter1, &voter2, &None, &None);
+}
+
+#[test]
+fn test_topic_delegation_matches_category() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let finance = symbol_short!("finance");
+    client.add_party(&party_a);
+    client.set_category(&finance);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter1, &voter3, &Some(finance.clone()), &None);
+
+    assert_eq!(client.get_category(), Some(finance.clone()));
+    assert_eq!(client.get_topic_delegate(&voter1, &finance), Some(voter3.clone()));
+    assert_eq!(client.get_delegated_power(&voter2), 0);
+    assert_eq!(client.get_delegated_power(&voter3), 1);
+
+    client.vote(&voter2, &party_a);
+    client.vote(&voter3, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 3);
+}
+
+#[test]
+fn test_topic_delegation_falls_back_to_global() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.set_category(&symbol_short!("tech"));
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter1, &voter3, &Some(symbol_short!("finance")), &None);
+
+    assert_eq!(client.get_delegated_power(&voter2), 1);
+    assert_eq!(client.get_delegated_power(&voter3), 0);
+
+    client.vote(&voter2, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 2);
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_set_category_after_voting_started() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.vote(&voter1, &party_a);
+
+    client.set_category(&symbol_short!("budget"));
+}
+
+#[test]
+fn test_topic_delegation_other_category_votes_directly() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.set_category(&symbol_short!("tech"));
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    client.delegate(&voter1, &voter2, &Some(symbol_short!("finance")), &None);
+    client.vote(&voter1, &party_a);
+    client.vote(&voter2, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 2);
+}
+
+#[test]
+#[should_panic(expected = "Voter has delegated their vote")]
+fn test_topic_delegated_voter_cannot_vote() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let finance = symbol_short!("finance");
+    client.add_party(&party_a);
+    client.set_category(&finance);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    client.delegate(&voter1, &voter2, &Some(finance), &None);
+    client.vote(&voter1, &party_a);
+}
+
+#[test]
+fn test_delegate_override_before_delegate_votes() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+    client.set_delegate_override(&true);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter3, &voter2, &None, &None);
+    client.vote(&voter1, &party_b);
+    client.vote(&voter2, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 2);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Voted);
+}
+
+#[test]
+fn test_delegate_override_after_delegate_votes() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+    client.set_delegate_override(&true);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.delegate(&voter2, &voter3, &None, &None);
+    client.vote(&voter3, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 3);
+    assert_eq!(client.get_delegated_power(&voter3), 2);
+
+    client.vote(&voter2, &party_b);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 2);
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 2);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+}
+
+#[test]
+fn test_delegation_expiry_timestamp() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let expiry = Deadline::Timestamp(env.ledger().timestamp() + 100);
+    client.delegate(&voter1, &voter2, &None, &Some(expiry.clone()));
+
+    assert_eq!(client.get_delegation(&voter1), Some((voter2.clone(), Some(expiry))));
+    assert_eq!(client.get_delegated_power(&voter2), 1);
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+
+    assert_eq!(client.get_delegation(&voter1), None);
+    assert_eq!(client.get_delegated_power(&voter2), 0);
+
+    client.vote(&voter1, &party_b);
+    client.vote(&voter2, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+}
+
+#[test]
+fn test_delegation_expiry_ledger() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let expiry = Deadline::Ledger(env.ledger().sequence() + 10);
+    client.delegate(&voter1, &voter2, &None, &Some(expiry));
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+}
+
+#[test]
+fn test_topic_delegation_renewed_after_expiry() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let topic = symbol_short!("Budget");
+    client.add_party(&party_a);
+    client.set_category(&topic);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    let expiry = Deadline::Ledger(env.ledger().sequence() + 10);
+    client.delegate(&voter1, &voter2, &Some(topic.clone()), &Some(expiry));
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+
+    client.delegate(&voter1, &voter3, &Some(topic.clone()), &None);
+    assert_eq!(client.get_topic_delegate(&voter1, &topic), Some(voter3.clone()));
+
+    client.vote(&voter3, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 2);
+}
+
+#[test]
+#[should_panic(expected = "Voter has delegated their vote")]
+fn test_expired_delegation_already_counted() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let expiry = Deadline::Timestamp(env.ledger().timestamp() + 100);
+    client.delegate(&voter1, &voter2, &None, &Some(expiry));
+    client.vote(&voter2, &party_a);
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+
+    client.vote(&voter1, &party_a);
+}
+
+#[test]
+fn test_abstain_and_blank_votes() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.vote(&voter1, &party_a);
+    client.vote(&voter2, &ABSTAIN);
+    client.vote(&voter3, &NOTA);
+
+    let stats = client.get_voting_stats();
+    assert_eq!(stats.total_votes, 1);
+    assert_eq!(stats.abstain_votes, 1);
+    assert_eq!(stats.blank_votes, 1);
+    assert_eq!(stats.turnout, 3);
+
+    let results = client.get_all_results();
+    assert_eq!(results.get(party_a).unwrap(), 1);
+    assert_eq!(results.get(ABSTAIN).unwrap(), 1);
+    assert_eq!(results.get(NOTA).unwrap(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Party name is reserved")]
+fn test_add_reserved_party() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_party(&NOTA);
+}
+
+#[test]
+fn test_winner_with_nota() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    env.mock_all_auths();
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+
+    for nota_competes in [false, true] {
+        let contract_id = env.register(VotingContract, ());
+        let client = VotingContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin);
+        client.add_party(&party_a);
+        client.add_party(&party_b);
+        client.add_voter(&voter1);
+        client.add_voter(&voter2);
+        client.add_voter(&voter3);
+        client.set_nota_competes(&nota_competes);
+
+        assert_eq!(client.get_winner(), None);
+
+        client.vote(&voter1, &party_b);
+        client.vote(&voter2, &NOTA);
+        client.vote(&voter3, &NOTA);
+
+        let expected = if nota_competes { NOTA } else { party_b.clone() };
+        assert_eq!(client.get_winner(), Some(expected));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_set_delegate_override_after_voting_started() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.vote(&voter2, &party_a);
+
+    client.set_delegate_override(&true);
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_set_nota_competes_after_voting_started() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    client.add_voter(&voter1);
+    client.vote(&voter1, &NOTA);
+
+    client.set_nota_competes(&true);
+}
+
+mod voting_v2 {
+    soroban_sdk::contractimport!(file = "testdata/voting_v2.wasm");
+}
+
+#[test]
+fn test_upgrade_keeps_votes() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.vote(&voter1, &party_a);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(voting_v2::WASM);
+    client.upgrade(&new_wasm_hash);
+
+    let upgraded = voting_v2::Client::new(&env, &contract_id);
+    assert_eq!(upgraded.version(), 2);
+    assert_eq!(upgraded.get_vote_count(&party_a), 1);
+}
+
+#[test]
+fn test_migrate_from_v1() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.remove(&DataKey::SchemaVersion);
+        storage.set(&DataKey::VoterStatus(voter1.clone()), &VoterStatus::Delegated(voter2.clone()));
+        storage.set(&DataKey::VoterStatus(voter3.clone()), &VoterStatus::Delegated(voter2.clone()));
+        storage.set(&DataKey::DelegatedVotes(voter2.clone()), &2u32);
+        storage.set(&DataKey::VotingDeadline, &(env.ledger().timestamp() + 100));
+    });
+
+    assert_eq!(client.get_schema_version(), 1);
+    assert_eq!(client.get_delegated_power(&voter2), 0);
+
+    let mut voters = [voter1.clone(), voter2.clone(), voter3.clone()];
+    voters.sort();
+
+    assert_eq!(client.migrate(&soroban_sdk::vec![&env, voters[0].clone(), voters[1].clone()]), 1);
+    assert_eq!(client.get_schema_version(), 1);
+
+    assert_eq!(client.migrate(&soroban_sdk::vec![&env, voters[2].clone()]), 0);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+    assert_eq!(client.get_delegated_power(&voter2), 2);
+    assert_eq!(client.get_voting_deadline(), Some(Deadline::Timestamp(env.ledger().timestamp() + 100)));
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::DelegatedVotes(voter2.clone())));
+    });
+
+    client.vote(&voter2, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 3);
+    assert_eq!(client.get_voting_stats().turnout, 3);
+}
+
+#[test]
+#[should_panic(expected = "Voters must be in ascending order and not yet migrated")]
+fn test_migrate_rejects_repeated_voter() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+    });
+
+    let mut voters = [voter1, voter2];
+    voters.sort();
+
+    client.migrate(&soroban_sdk::vec![&env, voters[0].clone()]);
+    client.migrate(&soroban_sdk::vec![&env, voters[0].clone()]);
+}
+
+#[test]
+#[should_panic(expected = "Contract already migrated")]
+fn test_migrate_twice() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.migrate(&Vec::new(&env));
+}
+
+#[test]
+#[should_panic(expected = "Contract is paused")]
+fn test_pause_blocks_voting() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    client.pause(&admin);
+    client.vote(&voter1, &party_a);
+}
+
+#[test]
+fn test_guardian_pause_and_unpause() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let guardian = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.vote(&voter1, &party_a);
+
+    client.set_guardian(&guardian);
+    client.pause(&guardian);
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (contract_id.clone(), (symbol_short!("paused"),).into_val(&env), guardian.into_val(&env)),
+        ]
+    );
+
+    assert!(client.is_paused());
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_voting_stats().turnout, 1);
+
+    client.unpause();
+    assert!(!client.is_paused());
+
+    client.vote(&voter2, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 2);
+}
+
+#[test]
+fn test_unpause_extends_deadline_by_paused_time() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let now = env.ledger().timestamp();
+    client.set_voting_deadline(&Deadline::Timestamp(now + 100), &String::from_str(&env, "Initial"));
+
+    env.ledger().with_mut(|li| li.timestamp = now + 40);
+    client.pause(&admin);
+    env.ledger().with_mut(|li| li.timestamp = now + 160);
+    client.unpause();
+
+    assert_eq!(client.get_voting_deadline(), Some(Deadline::Timestamp(now + 220)));
+    let history = client.get_deadline_history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().reason, String::from_str(&env, "Extended for pause"));
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+}
+
+#[test]
+#[should_panic(expected = "Contract is paused")]
+fn test_pause_blocks_cancel() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&100);
+    let action_id = client.schedule(&AdminAction::AddParty(symbol_short!("PartyA")));
+
+    client.pause(&admin);
+    client.cancel(&action_id);
+}
+
+#[test]
+#[should_panic(expected = "Caller cannot pause the contract")]
+fn test_pause_requires_admin_or_guardian() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.pause(&voter1);
+}
+
+#[test]
+fn test_deadline_extension_history() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let start = env.ledger().timestamp();
+    let initial = String::from_str(&env, "Initial deadline");
+    let extended = String::from_str(&env, "Network outage");
+
+    client.set_voting_deadline(&Deadline::Timestamp(start + 100), &initial);
+    client.set_max_deadline_extension(&50);
+    client.vote(&voter1, &party_a);
+    client.set_voting_deadline(&Deadline::Timestamp(start + 150), &extended);
+
+    assert_eq!(client.get_voting_deadline(), Some(Deadline::Timestamp(start + 150)));
+
+    let history = client.get_deadline_history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap(), DeadlineChange {
+        previous: PreviousDeadline::Unset,
+        deadline: Deadline::Timestamp(start + 100),
+        reason: initial,
+        changed_at: start,
+    });
+    assert_eq!(history.get(1).unwrap(), DeadlineChange {
+        previous: PreviousDeadline::Set(Deadline::Timestamp(start + 100)),
+        deadline: Deadline::Timestamp(start + 150),
+        reason: extended,
+        changed_at: start,
+    });
+}
+
+#[test]
+#[should_panic(expected = "Deadline can only be extended once voting has started")]
+fn test_deadline_cannot_shorten_after_voting_started() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let start = env.ledger().timestamp();
+    client.set_voting_deadline(&Deadline::Timestamp(start + 100), &String::from_str(&env, "Initial deadline"));
+    client.vote(&voter1, &party_a);
+    client.set_voting_deadline(&Deadline::Timestamp(start + 50), &String::from_str(&env, "Cut off"));
+}
+
+#[test]
+#[should_panic(expected = "Deadline extension exceeds maximum")]
+fn test_deadline_extension_exceeds_maximum() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let start = env.ledger().timestamp();
+    client.set_voting_deadline(&Deadline::Timestamp(start + 100), &String::from_str(&env, "Initial deadline"));
+    client.set_max_deadline_extension(&50);
+    client.vote(&voter1, &party_a);
+    client.set_voting_deadline(&Deadline::Timestamp(start + 200), &String::from_str(&env, "Extension"));
+}
+
+#[test]
+#[should_panic(expected = "Deadline extension exceeds maximum")]
+fn test_deadline_extension_maximum_is_cumulative() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let start = env.ledger().timestamp();
+    client.set_voting_deadline(&Deadline::Timestamp(start + 100), &String::from_str(&env, "Initial deadline"));
+    client.set_max_deadline_extension(&50);
+    client.vote(&voter1, &party_a);
+    client.set_voting_deadline(&Deadline::Timestamp(start + 130), &String::from_str(&env, "Extension"));
+    client.set_voting_deadline(&Deadline::Timestamp(start + 160), &String::from_str(&env, "Extension"));
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_max_deadline_extension_frozen_after_start() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.set_max_deadline_extension(&50);
+    client.vote(&voter1, &party_a);
+
+    client.set_max_deadline_extension(&500);
+}
+
+#[test]
+#[should_panic(expected = "Voting period has ended")]
+fn test_deadline_change_after_close() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let start = env.ledger().timestamp();
+    client.set_voting_deadline(&Deadline::Timestamp(start + 100), &String::from_str(&env, "Initial deadline"));
+
+    env.ledger().with_mut(|li| li.timestamp = start + 101);
+
+    client.set_voting_deadline(&Deadline::Timestamp(start + 500), &String::from_str(&env, "Reopen"));
+}
+
+#[test]
+fn test_ledger_voting_window() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let sequence = env.ledger().sequence();
+    client.set_voting_start(&Deadline::Ledger(sequence + 10));
+    client.set_voting_deadline(&Deadline::Ledger(sequence + 20), &String::from_str(&env, "Initial deadline"));
+    assert_eq!(client.get_phase(), Phase::Pending);
+
+    env.ledger().with_mut(|li| li.sequence_number = sequence + 10);
+    assert_eq!(client.get_phase(), Phase::Open);
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+
+    env.ledger().with_mut(|li| li.sequence_number = sequence + 21);
+    assert_eq!(client.get_phase(), Phase::Closed);
+}
+
+#[test]
+#[should_panic(expected = "Voting has not started")]
+fn test_voting_before_start() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    client.set_voting_start(&Deadline::Ledger(env.ledger().sequence() + 10));
+    client.vote(&voter1, &party_a);
+}
+
+#[test]
+#[should_panic(expected = "Voting period has ended")]
+fn test_delegate_after_ledger_deadline() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let deadline = env.ledger().sequence() + 5;
+    client.set_voting_deadline(&Deadline::Ledger(deadline), &String::from_str(&env, "Initial deadline"));
+
+    env.ledger().with_mut(|li| li.sequence_number = deadline + 1);
+
+    client.delegate(&voter1, &voter2, &None, &None);
+}
+
+#[test]
+fn test_hidden_results_revealed_after_deadline() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
+    client.set_hide_results(&true);
+
+    client.vote(&voter1, &party_a);
+    client.vote(&voter2, &NOTA);
+
+    let stats = client.get_voting_stats();
+    assert_eq!(stats.turnout, 2);
+    assert_eq!(stats.total_votes, 0);
+    assert_eq!(stats.blank_votes, 0);
+    assert!(client.try_get_vote_count(&party_a).is_err());
+    assert!(client.try_get_all_results().is_err());
+    assert!(client.try_get_winner().is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+
+    let stats = client.get_voting_stats();
+    assert_eq!(stats.total_votes, 1);
+    assert_eq!(stats.blank_votes, 1);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_winner(), Some(party_a));
+}
+
+#[test]
+#[should_panic(expected = "Results are hidden until the deadline")]
+fn test_hidden_results_vote_count() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    let deadline = env.ledger().timestamp() + 100;
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
+    client.set_hide_results(&true);
+    client.vote(&voter1, &party_a);
+
+    client.get_vote_count(&party_a);
+}
+
+#[test]
+#[should_panic(expected = "Hidden results require a deadline")]
+fn test_hidden_results_require_deadline() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    client.set_hide_results(&true);
+}
+
+fn scalar(env: &Env, value: u32) -> Fr {
+    Fr::from_u256(U256::from_u32(env, value))
+}
+
+fn encrypt(env: &Env, public_key: &G1Affine, value: u32, randomness: u32) -> Ciphertext {
+    let bls = env.crypto().bls12_381();
+    let (generator, _) = encryption_generators(env);
+    Ciphertext {
+        c1: bls.g1_mul(&generator, &scalar(env, randomness)).to_bytes(),
+        c2: bls.g1_add(
+            &bls.g1_mul(&generator, &scalar(env, value)),
+            &bls.g1_mul(public_key, &scalar(env, randomness)),
+        ).to_bytes(),
+    }
+}
+
+fn prove_equality(
+    env: &Env,
+    election: &Address,
+    voter: &Address,
+    public_key: &G1Affine,
+    c1: &G1Affine,
+    c2: &G1Affine,
+    randomness: &Fr,
+) -> EqualityProof {
+    let bls = env.crypto().bls12_381();
+    let (generator, _) = encryption_generators(env);
+    let nonce = scalar(env, 987_654);
+    let a = bls.g1_mul(&generator, &nonce);
+    let b = bls.g1_mul(public_key, &nonce);
+    let challenge = proof_challenge(env, election, voter, &[c1.clone(), c2.clone(), a, b]);
+    EqualityProof {
+        challenge: challenge.to_u256(),
+        response: (nonce + challenge * randomness.clone()).to_u256(),
+    }
+}
+
+/// Encrypts `value` (zero or `weight`) and proves it is one of the two.
+fn encrypt_choice(
+    env: &Env,
+    election: &Address,
+    voter: &Address,
+    public_key: &G1Affine,
+    value: u32,
+    weight: u32,
+    randomness: u32,
+) -> (Ciphertext, ChoiceProof) {
+    let bls = env.crypto().bls12_381();
+    let (generator, _) = encryption_generators(env);
+    let ciphertext = encrypt(env, public_key, value, randomness);
+    let c1 = G1Affine::from_bytes(ciphertext.c1.clone());
+    let c2 = G1Affine::from_bytes(ciphertext.c2.clone());
+    let c2_minus_weight = bls.g1_add(&c2, &-&bls.g1_mul(&generator, &scalar(env, weight)));
+
+    // Simulate the false branch, then answer the real one with the remainder
+    // of the challenge.
+    let fake_c2 = if value == 0 { &c2_minus_weight } else { &c2 };
+    let fake = EqualityProof {
+        challenge: scalar(env, 1234).to_u256(),
+        response: scalar(env, 5678).to_u256(),
+    };
+    let (fake_a, fake_b) = VotingContract::equality_commitments(env, &fake, public_key, &c1, fake_c2);
+
+    let nonce = scalar(env, 4321);
+    let real_a = bls.g1_mul(&generator, &nonce);
+    let real_b = bls.g1_mul(public_key, &nonce);
+    let points = if value == 0 {
+        [c1.clone(), c2.clone(), real_a, real_b, fake_a, fake_b]
+    } else {
+        [c1.clone(), c2.clone(), fake_a, fake_b, real_a, real_b]
+    };
+    let real_challenge = proof_challenge(env, election, voter, &points) - Fr::from_u256(fake.challenge.clone());
+    let real = EqualityProof {
+        challenge: real_challenge.to_u256(),
+        response: (nonce + real_challenge * scalar(env, randomness)).to_u256(),
+    };
+
+    let proof = if value == 0 {
+        ChoiceProof { zero: real, weight: fake }
+    } else {
+        ChoiceProof { zero: fake, weight: real }
+    };
+    (ciphertext, proof)
+}
+
+/// Builds a full encrypted ballot giving `weight` to the party at `choice`.
+fn encrypted_ballot(
+    env: &Env,
+    election: &Address,
+    voter: &Address,
+    public_key: &G1Affine,
+    parties: u32,
+    choice: u32,
+    weight: u32,
+) -> (Vec<Ciphertext>, Vec<ChoiceProof>, EqualityProof) {
+    let bls = env.crypto().bls12_381();
+    let (generator, _) = encryption_generators(env);
+    let mut ballot = Vec::new(env);
+    let mut proofs = Vec::new(env);
+    let mut randomness_sum = scalar(env, 0);
+    let mut c1_sum = G1Affine::from_bytes(BytesN::from_array(env, &G1_IDENTITY));
+    let mut c2_sum = G1Affine::from_bytes(BytesN::from_array(env, &G1_IDENTITY));
+    for i in 0..parties {
+        let value = if i == choice { weight } else { 0 };
+        let randomness = 11 + 7 * i;
+        let (ciphertext, proof) = encrypt_choice(env, election, voter, public_key, value, weight, randomness);
+        c1_sum = bls.g1_add(&c1_sum, &G1Affine::from_bytes(ciphertext.c1.clone()));
+        c2_sum = bls.g1_add(&c2_sum, &G1Affine::from_bytes(ciphertext.c2.clone()));
+        randomness_sum = randomness_sum + scalar(env, randomness);
+        ballot.push_back(ciphertext);
+        proofs.push_back(proof);
+    }
+
+    let c2_minus_weight = bls.g1_add(&c2_sum, &-&bls.g1_mul(&generator, &scalar(env, weight)));
+    let sum_proof = prove_equality(env, election, voter, public_key, &c1_sum, &c2_minus_weight, &randomness_sum);
+    (ballot, proofs, sum_proof)
+}
+
+fn setup_encrypted_election(env: &Env, client: &VotingContractClient, trustee: &Address, secret_key: u32) -> G1Affine {
+    let bls = env.crypto().bls12_381();
+    let (generator, pairing_base) = encryption_generators(env);
+    let public_key_g1 = bls.g1_mul(&generator, &scalar(env, secret_key));
+    let public_key_g2 = bls.g2_mul(&pairing_base, &scalar(env, secret_key));
+
+    client.enable_encrypted_ballots(trustee, &public_key_g1.to_bytes(), &public_key_g2.to_bytes());
+    public_key_g1
+}
+
+#[test]
+fn test_encrypted_ballots_tally() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let trustee = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    let secret_key = 424242;
+    let public_key = setup_encrypted_election(&env, &client, &trustee, secret_key);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
+
+    let (ballot, proofs, sum_proof) = encrypted_ballot(&env, &contract_id, &voter1, &public_key, 2, 0, 1);
+    client.vote_encrypted(&voter1, &ballot, &proofs, &sum_proof);
+
+    client.delegate(&voter3, &voter2, &None, &None);
+    let (ballot, proofs, sum_proof) = encrypted_ballot(&env, &contract_id, &voter2, &public_key, 2, 1, 2);
+    client.vote_encrypted(&voter2, &ballot, &proofs, &sum_proof);
+
+    assert_eq!(client.get_vote_count(&party_a), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+
+    let bls = env.crypto().bls12_381();
+    let mut shares = Vec::new(&env);
+    for party in client.get_parties().iter() {
+        let tally = client.get_encrypted_tally(&party).unwrap();
+        let share = bls.g1_mul(&G1Affine::from_bytes(tally.c1), &scalar(&env, secret_key));
+        shares.push_back(share.to_bytes());
+    }
+
+    client.publish_tally(&soroban_sdk::vec![&env, 1, 2], &shares);
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 2);
+    assert_eq!(client.get_winner(), Some(party_b));
+}
+
+#[test]
+#[should_panic(expected = "Invalid decryption proof")]
+fn test_encrypted_tally_rejects_wrong_result() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let trustee = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let secret_key = 7;
+    let public_key = setup_encrypted_election(&env, &client, &trustee, secret_key);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
+    let (ballot, proofs, sum_proof) = encrypted_ballot(&env, &contract_id, &voter1, &public_key, 1, 0, 1);
+    client.vote_encrypted(&voter1, &ballot, &proofs, &sum_proof);
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+
+    let tally = client.get_encrypted_tally(&party_a).unwrap();
+    let share = env.crypto().bls12_381().g1_mul(&G1Affine::from_bytes(tally.c1), &scalar(&env, secret_key));
+    client.publish_tally(&soroban_sdk::vec![&env, 5], &soroban_sdk::vec![&env, share.to_bytes()]);
+}
+
+#[test]
+#[should_panic(expected = "Ballot does not encrypt the voter's weight")]
+fn test_encrypted_ballot_with_extra_weight() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let trustee = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.add_party(&symbol_short!("PartyB"));
+    client.add_voter(&voter1);
+
+    let public_key = setup_encrypted_election(&env, &client, &trustee, 99);
+
+    // Both entries are valid "weight" choices, but together they give the
+    // voter twice their weight.
+    let bls = env.crypto().bls12_381();
+    let (generator, _) = encryption_generators(&env);
+    let (first, first_proof) = encrypt_choice(&env, &contract_id, &voter1, &public_key, 1, 1, 1);
+    let (second, second_proof) = encrypt_choice(&env, &contract_id, &voter1, &public_key, 1, 1, 2);
+    let c1_sum = bls.g1_add(&G1Affine::from_bytes(first.c1.clone()), &G1Affine::from_bytes(second.c1.clone()));
+    let c2_sum = bls.g1_add(&G1Affine::from_bytes(first.c2.clone()), &G1Affine::from_bytes(second.c2.clone()));
+    let c2_minus_weight = bls.g1_add(&c2_sum, &-&generator);
+    let sum_proof = prove_equality(&env, &contract_id, &voter1, &public_key, &c1_sum, &c2_minus_weight, &scalar(&env, 3));
+
+    client.vote_encrypted(
+        &voter1,
+        &soroban_sdk::vec![&env, first, second],
+        &soroban_sdk::vec![&env, first_proof, second_proof],
+        &sum_proof,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid ballot proof")]
+fn test_encrypted_ballot_entry_outside_range() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let trustee = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.add_party(&symbol_short!("PartyB"));
+    client.add_voter(&voter1);
+
+    let public_key = setup_encrypted_election(&env, &client, &trustee, 99);
+
+    // An entry of 2 could be balanced by a negative one to keep the sum right;
+    // the per-entry proof must reject it.
+    let (inflated, inflated_proof) = encrypt_choice(&env, &contract_id, &voter1, &public_key, 2, 1, 1);
+    let (empty, empty_proof) = encrypt_choice(&env, &contract_id, &voter1, &public_key, 0, 1, 2);
+    let (_, _, sum_proof) = encrypted_ballot(&env, &contract_id, &voter1, &public_key, 2, 0, 1);
+    client.vote_encrypted(
+        &voter1,
+        &soroban_sdk::vec![&env, inflated, empty],
+        &soroban_sdk::vec![&env, inflated_proof, empty_proof],
+        &sum_proof,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Election uses encrypted ballots")]
+fn test_plain_vote_rejected_in_encrypted_mode() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let trustee = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    setup_encrypted_election(&env, &client, &trustee, 5);
+    client.vote(&voter1, &party_a);
+}
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&left.clone().into());
+    preimage.append(&right.clone().into());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+fn nullifier_for(client: &VotingContractClient, secret: &BytesN<32>) -> BytesN<32> {
+    let env = &client.env;
+    let mut preimage = Bytes::from_slice(env, b"nullifier");
+    preimage.append(&client.address.clone().to_xdr(env));
+    preimage.append(&secret.clone().into());
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+fn commit_anonymous(client: &VotingContractClient, party: &Symbol, secret: &BytesN<32>) {
+    let env = &client.env;
+    let mut preimage = Bytes::from_slice(env, b"stellar-vote:anonymous:v1");
+    preimage.append(&(client.address.clone(), party.clone(), secret.clone()).to_xdr(env));
+    client.commit_anonymous(&env.crypto().sha256(&preimage).to_bytes());
+}
+
+/// Commits to the vote, then reveals it in the next ledger.
+fn vote_anonymous(client: &VotingContractClient, party: &Symbol, proof: &MembershipProof) {
+    commit_anonymous(client, party, &proof.secret);
+    client.env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.vote_anonymous(party, &nullifier_for(client, &proof.secret), proof);
+}
+
+fn membership_tree(env: &Env, secrets: &[BytesN<32>; 4]) -> (BytesN<32>, [MembershipProof; 4]) {
+    let leaves: [BytesN<32>; 4] = secrets.clone().map(|secret| env.crypto().sha256(&secret.into()).to_bytes());
+    let left = hash_pair(env, &leaves[0], &leaves[1]);
+    let right = hash_pair(env, &leaves[2], &leaves[3]);
+    let root = hash_pair(env, &left, &right);
+
+    let proof = |index: u32, sibling: &BytesN<32>, uncle: &BytesN<32>| MembershipProof {
+        secret: secrets[index as usize].clone(),
+        index,
+        path: soroban_sdk::vec![env, sibling.clone(), uncle.clone()],
+    };
+    let proofs = [
+        proof(0, &leaves[1], &right),
+        proof(1, &leaves[0], &right),
+        proof(2, &leaves[3], &left),
+        proof(3, &leaves[2], &left),
+    ];
+    (root, proofs)
+}
+
+fn anonymous_secrets(env: &Env) -> [BytesN<32>; 4] {
+    [1u8, 2, 3, 4].map(|seed| BytesN::from_array(env, &[seed; 32]))
+}
+
+#[test]
+fn test_anonymous_voting() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    vote_anonymous(&client, &party_a, &proofs[0]);
+    vote_anonymous(&client, &party_b, &proofs[3]);
+    vote_anonymous(&client, &party_a, &proofs[2]);
+
+    assert!(client.is_nullifier_used(&nullifier_for(&client, &secrets[0])));
+    assert_eq!(client.get_vote_count(&party_a), 2);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+}
+
+#[test]
+#[should_panic(expected = "Nullifier already used")]
+fn test_anonymous_double_vote() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    vote_anonymous(&client, &party_a, &proofs[1]);
+    vote_anonymous(&client, &party_a, &proofs[1]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid membership proof")]
+fn test_anonymous_invalid_proof() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    let outsider = BytesN::from_array(&env, &[9u8; 32]);
+    let forged = MembershipProof {
+        secret: outsider.clone(),
+        index: 0,
+        path: proofs[0].path.clone(),
+    };
+    vote_anonymous(&client, &party_a, &forged);
+}
+
+#[test]
+#[should_panic(expected = "Ballot not committed")]
+fn test_anonymous_reveal_cannot_be_redirected() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    // A watcher who copies the revealed proof cannot vote for another party
+    // because the voter's commitment only matches PartyA.
+    commit_anonymous(&client, &party_a, &secrets[0]);
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.vote_anonymous(&party_b, &nullifier_for(&client, &secrets[0]), &proofs[0]);
+}
+
+#[test]
+#[should_panic(expected = "Ballot must be committed in an earlier ledger")]
+fn test_anonymous_commit_and_reveal_in_same_ledger() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    commit_anonymous(&client, &party_a, &secrets[0]);
+    client.vote_anonymous(&party_a, &nullifier_for(&client, &secrets[0]), &proofs[0]);
+}
+
+#[test]
+fn test_anonymous_commitment_lives_until_deadline() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+
+    let secrets = anonymous_secrets(&env);
+    let (root, proofs) = membership_tree(&env, &secrets);
+    client.set_voter_root(&root);
+
+    let deadline = env.ledger().sequence() + 1_000;
+    client.set_voting_deadline(&Deadline::Ledger(deadline), &String::from_str(&env, "Anonymous vote"));
+
+    commit_anonymous(&client, &party_a, &secrets[0]);
+    env.ledger().with_mut(|li| li.sequence_number = deadline);
+    client.vote_anonymous(&party_a, &nullifier_for(&client, &secrets[0]), &proofs[0]);
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+}
+
+#[test]
+#[should_panic(expected = "Election uses anonymous ballots")]
+fn test_plain_vote_rejected_in_anonymous_mode() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let (root, _) = membership_tree(&env, &anonymous_secrets(&env));
+    client.set_voter_root(&root);
+    client.vote(&voter1, &party_a);
+}
+
+fn signing_key(seed: u8) -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+}
+
+fn sign_ballot(
+    client: &VotingContractClient,
+    key: &ed25519_dalek::SigningKey,
+    party: &Symbol,
+    nonce: u64,
+    expiry: u64,
+) -> (BytesN<32>, BytesN<64>) {
+    use ed25519_dalek::Signer;
+
+    let env = &client.env;
+    let public_key = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    let message = client.get_ballot_message(&public_key, party, &nonce, &expiry);
+    let mut buffer = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buffer[..len]);
+    let signature = BytesN::from_array(env, &key.sign(&buffer[..len]).to_bytes());
+    (public_key, signature)
+}
+
+#[test]
+fn test_vote_by_signature() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let key = signing_key(1);
+    let (public_key, signature) = sign_ballot(&client, &key, &party_a, 0, 1000);
+    client.set_voter_key(&voter1, &public_key);
+    assert_eq!(client.get_key_voter(&public_key), Some(voter1.clone()));
+
+    env.set_auths(&[]);
+    client.vote_by_signature(&public_key, &party_a, &0, &1000, &signature);
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Voted);
+    assert_eq!(client.get_nonce(&voter1), 1);
+}
+
+#[test]
+fn test_voter_key_requires_voter_auth() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_voter(&voter1);
+
+    let public_key = BytesN::from_array(&env, &signing_key(1).verifying_key().to_bytes());
+    for signer in [&admin, &voter2, &voter1] {
+        let result = client
+            .mock_auths(&[MockAuth {
+                address: signer,
+                invoke: &MockAuthInvoke {
+                    contract: &contract_id,
+                    fn_name: "set_voter_key",
+                    args: (&voter1, &public_key).into_val(&env),
+                    sub_invokes: &[],
+                },
+            }])
+            .try_set_voter_key(&voter1, &public_key);
+        assert_eq!(result.is_ok(), *signer == voter1);
+    }
+    assert_eq!(client.get_key_voter(&public_key), Some(voter1));
+}
+
+#[test]
+fn test_voter_key_rotation_and_revocation() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_voter(&voter1);
+
+    let first = BytesN::from_array(&env, &signing_key(1).verifying_key().to_bytes());
+    let second = BytesN::from_array(&env, &signing_key(2).verifying_key().to_bytes());
+
+    client.set_voter_key(&voter1, &first);
+    client.set_voter_key(&voter1, &second);
+    assert_eq!(client.get_key_voter(&first), None);
+    assert_eq!(client.get_key_voter(&second), Some(voter1.clone()));
+    assert_eq!(client.get_voter_key(&voter1), Some(second.clone()));
+
+    client.revoke_voter_key(&voter1);
+    assert_eq!(client.get_key_voter(&second), None);
+    assert_eq!(client.get_voter_key(&voter1), None);
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_voter_key_frozen_after_voting_started() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    let (public_key, _) = sign_ballot(&client, &signing_key(1), &party_a, 0, 1000);
+    client.set_voter_key(&voter1, &public_key);
+    client.vote(&voter2, &party_a);
+
+    client.revoke_voter_key(&voter1);
+}
+
+#[test]
+#[should_panic(expected = "Invalid nonce")]
+fn test_vote_by_signature_rejects_replay() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let (public_key, signature) = sign_ballot(&client, &signing_key(1), &party_a, 0, 1000);
+    client.set_voter_key(&voter1, &public_key);
+
+    client.vote_by_signature(&public_key, &party_a, &0, &1000, &signature);
+    client.vote_by_signature(&public_key, &party_a, &0, &1000, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Signature expired")]
+fn test_vote_by_signature_rejects_expired() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let (public_key, signature) = sign_ballot(&client, &signing_key(1), &party_a, 0, 1000);
+    client.set_voter_key(&voter1, &public_key);
+
+    env.ledger().with_mut(|li| li.timestamp = 1001);
+    client.vote_by_signature(&public_key, &party_a, &0, &1000, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_vote_by_signature_rejects_tampered_party() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+
+    let (public_key, signature) = sign_ballot(&client, &signing_key(1), &party_a, 0, 1000);
+    client.set_voter_key(&voter1, &public_key);
+
+    client.vote_by_signature(&public_key, &party_b, &0, &1000, &signature);
+}
+
+fn signed_ballot(client: &VotingContractClient, key: &ed25519_dalek::SigningKey, party: &Symbol, nonce: u64) -> SignedBallot {
+    let (voter_pubkey, signature) = sign_ballot(client, key, party, nonce, 1000);
+    SignedBallot {
+        voter_pubkey,
+        party: party.clone(),
+        nonce,
+        expiry: 1000,
+        signature,
+    }
+}
+
+#[test]
+fn test_submit_ballots_reports_each_ballot() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    let (key1, key2, key3, stranger) = (signing_key(1), signing_key(2), signing_key(3), signing_key(4));
+    for (voter, key) in [(&voter1, &key1), (&voter2, &key2), (&voter3, &key3)] {
+        let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+        client.set_voter_key(voter, &public_key);
+    }
+
+    let ballots = vec![
+        &env,
+        signed_ballot(&client, &key1, &party_a, 0),
+        signed_ballot(&client, &key1, &party_b, 1),
+        signed_ballot(&client, &key2, &party_b, 5),
+        signed_ballot(&client, &key3, &symbol_short!("Unknown"), 0),
+        signed_ballot(&client, &stranger, &party_a, 0),
+        signed_ballot(&client, &key2, &party_b, 0),
+    ];
+    let results = client.submit_ballots(&ballots);
+
+    assert_eq!(results, vec![
+        &env,
+        BallotResult::Accepted,
+        BallotResult::AlreadyVoted,
+        BallotResult::InvalidNonce,
+        BallotResult::PartyNotFound,
+        BallotResult::UnknownKey,
+        BallotResult::Accepted,
+    ]);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+    assert_eq!(client.get_voter_status(&voter3), VoterStatus::Registered);
+}
+
+#[test]
+fn test_submit_ballots_isolates_invalid_signature() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+
+    let keys = [signing_key(1), signing_key(2), signing_key(3)];
+    for (voter, key) in [&voter1, &voter2, &voter3].into_iter().zip(keys.iter()) {
+        client.add_voter(voter);
+        client.set_voter_key(voter, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    }
+
+    let mut malformed = signed_ballot(&client, &keys[1], &party_a, 0);
+    malformed.signature = BytesN::from_array(&env, &[0xff; 64]);
+    let ballots = vec![
+        &env,
+        signed_ballot(&client, &keys[0], &party_a, 0),
+        malformed,
+        signed_ballot(&client, &keys[2], &party_b, 0),
+    ];
+    let results = client.submit_ballots(&ballots);
+
+    assert_eq!(results, vec![
+        &env,
+        BallotResult::Accepted,
+        BallotResult::InvalidSignature,
+        BallotResult::Accepted,
+    ]);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+    assert_eq!(client.get_voter_status(&voter2), VoterStatus::Registered);
+    assert_eq!(client.get_nonce(&voter2), 0);
+}
+
+#[test]
+fn test_submit_ballots_rejects_forged_signature() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+
+    let keys = [signing_key(1), signing_key(2)];
+    for (voter, key) in [&voter1, &voter2].into_iter().zip(keys.iter()) {
+        client.add_voter(voter);
+        client.set_voter_key(voter, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    }
+
+    let mut forged = signed_ballot(&client, &keys[1], &party_a, 0);
+    forged.party = party_b.clone();
+    let ballots = vec![&env, signed_ballot(&client, &keys[0], &party_a, 0), forged];
+    assert!(client.try_submit_ballots(&ballots).is_err());
+
+    assert_eq!(client.get_vote_count(&party_a), 0);
+    assert_eq!(client.get_vote_count(&party_b), 0);
+    assert_eq!(client.get_nonce(&voter2), 0);
+}
+
+#[test]
+fn test_submit_ballots_rejects_outside_voting_window() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+
+    let key = signing_key(1);
+    client.set_voter_key(&voter1, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    let start = env.ledger().timestamp() + 10;
+    client.set_voting_start(&Deadline::Timestamp(start));
+
+    let results = client.submit_ballots(&vec![&env, signed_ballot(&client, &key, &party_a, 0)]);
+    assert_eq!(results, vec![&env, BallotResult::NotOpen]);
+}
+
+fn setup_reward_election(env: &Env, admin: &Address, funding: i128) -> (VotingContractClient<'static>, token::Client<'static>) {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(env, "initial"));
+
+    let token_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(env, &token_id).mint(admin, &funding);
+
+    client.set_reward_pool(&token_id, &10, &50);
+    client.fund_rewards(admin, &funding);
+
+    (client, token::Client::new(env, &token_id))
+}
+
+#[test]
+fn test_claim_reward_for_direct_and_delegated_voters() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let (client, token) = setup_reward_election(&env, &admin, 100);
+    let party_a = symbol_short!("PartyA");
+
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+    client.delegate(&voter2, &voter1, &None, &None);
+    client.vote(&voter1, &party_a);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+
+    assert_eq!(client.claim_reward(&voter1), 10);
+    assert_eq!(client.claim_reward(&voter2), 10);
+    assert_eq!(token.balance(&voter1), 10);
+    assert_eq!(token.balance(&voter2), 10);
+    assert_eq!(client.get_reward_pool().unwrap().balance, 80);
+    assert!(client.has_claimed_reward(&voter1));
+    assert!(!client.has_claimed_reward(&voter3));
+}
+
+#[test]
+#[should_panic(expected = "Reward already claimed")]
+fn test_claim_reward_only_once() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_reward_election(&env, &admin, 100);
+
+    client.add_voter(&voter1);
+    client.vote(&voter1, &symbol_short!("PartyA"));
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+
+    client.claim_reward(&voter1);
+    client.claim_reward(&voter1);
+}
+
+#[test]
+#[should_panic(expected = "Election not finalized")]
+fn test_claim_reward_requires_finalization() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_reward_election(&env, &admin, 100);
+
+    client.add_voter(&voter1);
+    client.vote(&voter1, &symbol_short!("PartyA"));
+    client.claim_reward(&voter1);
+}
+
+#[test]
+fn test_reward_pool_exhaustion() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let (client, token) = setup_reward_election(&env, &admin, 15);
+    let party_a = symbol_short!("PartyA");
+
+    for voter in [&voter1, &voter2, &voter3] {
+        client.add_voter(voter);
+        client.vote(voter, &party_a);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+
+    assert_eq!(client.claim_reward(&voter1), 10);
+    assert_eq!(client.claim_reward(&voter2), 5);
+    assert_eq!(token.balance(&voter2), 5);
+
+    let result = client.try_claim_reward(&voter3);
+    assert!(result.is_err());
+    assert!(!client.has_claimed_reward(&voter3));
+}
+
+#[test]
+fn test_reclaim_rewards_after_claim_window() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let (client, token) = setup_reward_election(&env, &admin, 100);
+    let party_a = symbol_short!("PartyA");
+
+    for voter in [&voter1, &voter2] {
+        client.add_voter(voter);
+        client.vote(voter, &party_a);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+    assert_eq!(client.claim_reward(&voter1), 10);
+    assert!(client.try_reclaim_rewards().is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 152);
+    assert!(client.try_claim_reward(&voter2).is_err());
+    assert_eq!(client.reclaim_rewards(), 90);
+    assert_eq!(token.balance(&admin), 90);
+    assert_eq!(client.get_reward_pool().unwrap().balance, 0);
+}
+
+#[test]
+#[should_panic(expected = "Voting has already started")]
+fn test_reward_pool_frozen_after_voting_started() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, token) = setup_reward_election(&env, &admin, 100);
+
+    client.add_voter(&voter1);
+    client.vote(&voter1, &symbol_short!("PartyA"));
+
+    client.set_reward_pool(&token.address, &1_000, &50);
+}
+
+fn setup_bonded_election(env: &Env, admin: &Address, voters: &[&Address], scale_weight: bool) -> (VotingContractClient<'static>, token::Client<'static>) {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(env, "initial"));
+
+    let token_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let asset = token::StellarAssetClient::new(env, &token_id);
+    for voter in voters {
+        client.add_voter(voter);
+        asset.mint(voter, &1000);
+    }
+
+    client.enable_bonded_voting(&token_id, &100, &scale_weight);
+    (client, token::Client::new(env, &token_id))
+}
+
+#[test]
+fn test_bonded_vote_locks_tokens_until_deadline() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, token) = setup_bonded_election(&env, &admin, &[&voter1], false);
+    let party_a = symbol_short!("PartyA");
+
+    client.vote(&voter1, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_bond(&voter1), 100);
+    assert_eq!(token.balance(&voter1), 900);
+    assert_eq!(token.balance(&client.address), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.withdraw_bond(&voter1), 100);
+    assert_eq!(token.balance(&voter1), 1000);
+    assert_eq!(client.get_bond(&voter1), 0);
+}
+
+#[test]
+fn test_bonded_weight_scales_with_bond() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let (client, _) = setup_bonded_election(&env, &admin, &[&voter1, &voter2, &voter3], true);
+    let party_a = symbol_short!("PartyA");
+
+    client.delegate(&voter3, &voter1, &None, &None);
+    client.vote_bonded(&voter1, &party_a, &350);
+    client.vote(&voter2, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 5);
+    assert_eq!(client.get_bond(&voter1), 350);
+    assert_eq!(client.get_bond(&voter3), 100);
+}
+
+#[test]
+fn test_bonded_delegators_bond_their_own_weight() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let (client, token) = setup_bonded_election(&env, &admin, &[&voter1, &voter2, &voter3], false);
+    let party_a = symbol_short!("PartyA");
+
+    client.delegate(&voter2, &voter1, &None, &None);
+    client.delegate(&voter3, &voter1, &None, &None);
+    assert_eq!(client.get_bond(&voter2), 100);
+    assert_eq!(token.balance(&voter2), 900);
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 3);
+    assert_eq!(client.get_bond(&voter1), 100);
+    assert_eq!(token.balance(&voter1), 900);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.withdraw_bond(&voter1), 100);
+    assert_eq!(client.withdraw_bond(&voter2), 100);
+    assert_eq!(token.balance(&voter3), 900);
+}
+
+#[test]
+#[should_panic(expected = "Bonded voting requires a deadline")]
+fn test_bonded_voting_requires_deadline() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let token_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    client.enable_bonded_voting(&token_id, &100, &false);
+}
+
+#[test]
+#[should_panic(expected = "Bonds are locked until the deadline")]
+fn test_withdraw_bond_before_deadline() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_bonded_election(&env, &admin, &[&voter1], false);
+
+    client.vote(&voter1, &symbol_short!("PartyA"));
+    client.withdraw_bond(&voter1);
+}
+
+#[test]
+#[should_panic(expected = "Bond below required amount")]
+fn test_bonded_vote_below_minimum() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_bonded_election(&env, &admin, &[&voter1], true);
+
+    client.vote_bonded(&voter1, &symbol_short!("PartyA"), &50);
+}
+
+fn setup_gated_election(env: &Env, admin: &Address, holders: &[&Address]) -> VotingContractClient<'static> {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(env, "initial"));
+
+    let token_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let asset = token::StellarAssetClient::new(env, &token_id);
+    for holder in holders {
+        asset.mint(holder, &10);
+    }
+
+    client.set_eligibility_token(&token_id, &10);
+    client
+}
+
+#[contract]
+struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn balance(_env: Env, _owner: Address) -> i128 {
+        1
+    }
+}
+
+#[test]
+#[should_panic(expected = "Eligibility token must be a fungible token")]
+fn test_eligibility_token_rejects_nft() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+    client.set_eligibility_token(&env.register(MockNft, ()), &1);
+}
+
+#[test]
+fn test_token_holder_registered_on_vote() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let client = setup_gated_election(&env, &admin, &[&voter1]);
+    let party_a = symbol_short!("PartyA");
+
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::NotRegistered);
+    client.vote(&voter1, &party_a);
+
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Voted);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_voting_stats().total_voters, 1);
+}
+
+#[test]
+fn test_token_holders_registered_on_delegate() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let client = setup_gated_election(&env, &admin, &[&voter1, &voter2]);
+    let party_a = symbol_short!("PartyA");
+
+    client.register(&voter2);
+    client.delegate(&voter1, &voter2, &None, &None);
+    client.vote(&voter2, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 2);
+    assert_eq!(client.get_voting_stats().total_voters, 2);
+}
+
+#[test]
+fn test_gate_tokens_cannot_be_passed_on() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let client = setup_gated_election(&env, &admin, &[&voter1]);
+    let gate = token::Client::new(&env, &client.get_eligibility_token().unwrap().token);
+    let party_a = symbol_short!("PartyA");
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_eligibility_deposit(&voter1), 10);
+    assert_eq!(gate.balance(&voter1), 0);
+
+    assert!(gate.try_transfer(&voter1, &voter2, &10).is_err());
+    assert!(client.try_vote(&voter2, &party_a).is_err());
+    assert_eq!(client.get_vote_count(&party_a), 1);
+
+    assert!(client.try_withdraw_eligibility_deposit(&voter1).is_err());
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(client.withdraw_eligibility_deposit(&voter1), 10);
+    assert_eq!(gate.balance(&voter1), 10);
+}
+
+#[test]
+#[should_panic(expected = "Voter not registered")]
+fn test_vote_without_enough_tokens() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let client = setup_gated_election(&env, &admin, &[&voter1]);
+
+    client.vote(&voter2, &symbol_short!("PartyA"));
+}
+
+#[contract]
+struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_weight(env: Env, voter: Address, weight: i128) {
+        env.storage().instance().set(&voter, &weight);
+    }
+}
+
+#[contractimpl]
+impl EligibilityOracle for MockOracle {
+    fn is_eligible(env: Env, voter: Address, _election: Address) -> bool {
+        env.storage().instance().get::<_, i128>(&voter).unwrap_or(0) > 0
+    }
+
+    fn weight(env: Env, voter: Address, _election: Address) -> i128 {
+        env.storage().instance().get(&voter).unwrap_or(0)
+    }
+}
+
+fn setup_oracle_election(env: &Env, admin: &Address) -> (VotingContractClient<'static>, MockOracleClient<'static>) {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+    let oracle = MockOracleClient::new(env, &env.register(MockOracle, ()));
+
+    env.mock_all_auths();
+    client.initialize(admin);
+    client.add_party(&symbol_short!("PartyA"));
+    client.set_eligibility_oracle(&oracle.address);
+
+    (client, oracle)
+}
+
+#[test]
+fn test_oracle_eligibility_and_weight() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, oracle) = setup_oracle_election(&env, &admin);
+    let party_a = symbol_short!("PartyA");
+
+    oracle.set_weight(&voter1, &3);
+    client.vote(&voter1, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_a), 3);
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Voted);
+    assert_eq!(client.get_voting_stats().total_voters, 1);
+}
+
+#[test]
+fn test_oracle_weight_applies_to_encrypted_ballots() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    env.cost_estimate().budget().reset_unlimited();
+    let (client, oracle) = setup_oracle_election(&env, &admin);
+    let party_a = symbol_short!("PartyA");
+    let trustee = Address::generate(&env);
+
+    let secret_key = 31337;
+    let public_key = setup_encrypted_election(&env, &client, &trustee, secret_key);
+    let deadline = env.ledger().timestamp() + 100;
+    client.set_voting_deadline(&Deadline::Timestamp(deadline), &String::from_str(&env, "Initial deadline"));
+
+    oracle.set_weight(&voter1, &3);
+    let (ballot, proofs, sum_proof) = encrypted_ballot(&env, &client.address, &voter1, &public_key, 1, 0, 3);
+    client.vote_encrypted(&voter1, &ballot, &proofs, &sum_proof);
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    let tally = client.get_encrypted_tally(&party_a).unwrap();
+    let share = env.crypto().bls12_381().g1_mul(&G1Affine::from_bytes(tally.c1), &scalar(&env, secret_key));
+    client.publish_tally(&soroban_sdk::vec![&env, 3], &soroban_sdk::vec![&env, share.to_bytes()]);
+
+    assert_eq!(client.get_vote_count(&party_a), 3);
+}
+
+#[test]
+#[should_panic(expected = "Voter not eligible")]
+fn test_oracle_rejects_registered_voter() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_oracle_election(&env, &admin);
+
+    client.add_voter(&voter1);
+    client.vote(&voter1, &symbol_short!("PartyA"));
+}
+
+#[test]
+#[should_panic(expected = "Voter not eligible")]
+fn test_oracle_rejects_unlisted_voter() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_oracle_election(&env, &admin);
+
+    client.vote(&voter1, &symbol_short!("PartyA"));
+}
+
+#[test]
+fn test_oracle_weight_applies_to_delegators() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let (client, oracle) = setup_oracle_election(&env, &admin);
+    let party_a = symbol_short!("PartyA");
+
+    oracle.set_weight(&voter1, &1);
+    oracle.set_weight(&voter2, &3);
+    oracle.set_weight(&voter3, &2);
+    client.delegate(&voter2, &voter1, &None, &None);
+    client.delegate(&voter3, &voter1, &None, &None);
+    assert_eq!(client.get_delegated_power(&voter1), 5);
+
+    oracle.set_weight(&voter3, &0);
+    assert_eq!(client.get_delegated_power(&voter1), 3);
+
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 4);
+}
+
+#[test]
+fn test_oracle_weight_taken_back_on_override() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let (client, oracle) = setup_oracle_election(&env, &admin);
+    let party_a = symbol_short!("PartyA");
+
+    client.add_party(&symbol_short!("PartyB"));
+    client.set_delegate_override(&true);
+    oracle.set_weight(&voter1, &1);
+    oracle.set_weight(&voter2, &3);
+    client.delegate(&voter2, &voter1, &None, &None);
+    client.vote(&voter1, &party_a);
+    assert_eq!(client.get_vote_count(&party_a), 4);
+
+    oracle.set_weight(&voter2, &5);
+    client.vote(&voter2, &symbol_short!("PartyB"));
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&symbol_short!("PartyB")), 5);
+}
+
+#[contract]
+struct Counter;
+
+#[contractimpl]
+impl Counter {
+    pub fn increment(env: Env, by: u32) {
+        let count: u32 = env.storage().instance().get(&symbol_short!("count")).unwrap_or(0);
+        env.storage().instance().set(&symbol_short!("count"), &(count + by));
+    }
+
+    pub fn count(env: Env) -> u32 {
+        env.storage().instance().get(&symbol_short!("count")).unwrap_or(0)
+    }
+}
+
+fn setup_governance(env: &Env, admin: &Address, voter: &Address, choice: &Symbol) -> (VotingContractClient<'static>, CounterClient<'static>) {
+    let (client, counter) = setup_proposals(env, admin);
+    client.add_voter(voter);
+    client.vote(voter, choice);
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+
+    (client, counter)
+}
+
+fn setup_proposals(env: &Env, admin: &Address) -> (VotingContractClient<'static>, CounterClient<'static>) {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+    let counter = CounterClient::new(env, &env.register(Counter, ()));
+
+    env.mock_all_auths();
+    client.initialize(admin);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(env, "initial"));
+    client.set_execution_delay(&50);
+
+    for (party, by) in [(&party_a, 5u32), (&party_b, 7u32)] {
+        client.set_proposal(party, &vec![
+            env,
+            Invocation {
+                contract: counter.address.clone(),
+                function: Symbol::new(env, "increment"),
+                args: vec![env, by.into_val(env)],
+            },
+        ]);
+    }
+
+    (client, counter)
+}
+
+fn finalize_governance(env: &Env, client: &VotingContractClient, ballots: &[(&Address, Symbol)]) {
+    for (voter, choice) in ballots {
+        client.add_voter(voter);
+        client.vote(voter, choice);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+    env.ledger().with_mut(|li| li.timestamp = 151);
+}
+
+#[test]
+fn test_execute_passed_proposal_after_timelock() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, counter) = setup_governance(&env, &admin, &voter1, &party_a);
+
+    assert_eq!(client.get_finalized_at(), Some(101));
+
+    env.ledger().with_mut(|li| li.timestamp = 151);
+    client.execute(&party_a);
+
+    assert_eq!(counter.count(), 5);
+    assert!(client.is_proposal_executed(&party_a));
+    assert!(client.try_execute(&party_a).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Timelock has not expired")]
+fn test_execute_before_timelock() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, _) = setup_governance(&env, &admin, &voter1, &party_a);
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    client.execute(&party_a);
+}
+
+#[test]
+#[should_panic(expected = "Proposal did not pass")]
+fn test_execute_losing_proposal() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, _) = setup_governance(&env, &admin, &voter1, &symbol_short!("PartyA"));
+
+    env.ledger().with_mut(|li| li.timestamp = 151);
+    client.execute(&symbol_short!("PartyB"));
+}
+
+#[test]
+fn test_execute_uses_final_result() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    let (client, counter) = setup_governance(&env, &admin, &voter1, &party_a);
+
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::Votes(party_b.clone()), &5u32);
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 151);
+    assert!(client.try_execute(&party_b).is_err());
+    client.execute(&party_a);
+    assert_eq!(counter.count(), 5);
+}
+
+#[test]
+#[should_panic(expected = "Proposal is tied")]
+fn test_execute_rejects_tie() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, _) = setup_proposals(&env, &admin);
+
+    finalize_governance(&env, &client, &[(&voter1, party_a.clone()), (&voter2, symbol_short!("PartyB"))]);
+    client.execute(&party_a);
+}
+
+#[test]
+#[should_panic(expected = "Quorum not reached")]
+fn test_execute_requires_quorum() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, _) = setup_proposals(&env, &admin);
+    client.set_proposal_threshold(&2, &0);
+
+    finalize_governance(&env, &client, &[(&voter1, party_a.clone())]);
+    client.execute(&party_a);
+}
+
+#[test]
+fn test_execute_meets_majority() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, counter) = setup_proposals(&env, &admin);
+    client.set_proposal_threshold(&3, &6000);
+    assert_eq!(client.get_proposal_threshold(), ProposalThreshold { quorum: 3, majority_bps: 6000 });
+
+    finalize_governance(&env, &client, &[
+        (&voter1, party_a.clone()),
+        (&voter2, party_a.clone()),
+        (&voter3, ABSTAIN),
+    ]);
+    client.execute(&party_a);
+    assert_eq!(counter.count(), 5);
+}
+
+#[test]
+#[should_panic(expected = "Majority not reached")]
+fn test_execute_requires_majority() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, _) = setup_proposals(&env, &admin);
+    client.set_proposal_threshold(&0, &7000);
+
+    finalize_governance(&env, &client, &[
+        (&voter1, party_a.clone()),
+        (&voter2, party_a.clone()),
+        (&voter3, ABSTAIN),
+    ]);
+    client.execute(&party_a);
+}
+
+#[test]
+fn test_scheduled_admin_action_executes_after_delay() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&3600);
+
+    let party_a = symbol_short!("PartyA");
+    let party_id = client.schedule(&AdminAction::AddParty(party_a.clone()));
+    let voter_id = client.schedule(&AdminAction::AddVoter(voter1.clone()));
+
+    let scheduled = client.get_scheduled_actions();
+    assert_eq!(scheduled.len(), 2);
+    assert_eq!(scheduled.get(party_id).unwrap().ready_at, 3600);
+    assert!(client.try_execute_scheduled(&party_id).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.execute_scheduled(&party_id);
+    client.execute_scheduled(&voter_id);
+
+    assert_eq!(client.get_parties(), vec![&env, party_a]);
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Registered);
+    assert_eq!(client.get_scheduled_actions().len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Admin action must be scheduled")]
+fn test_direct_admin_action_blocked_by_delay() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&3600);
+    client.add_party(&symbol_short!("PartyA"));
+}
+
+#[test]
+fn test_admin_setters_require_schedule_under_delay() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let token = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_voting_deadline(&Deadline::Timestamp(7200), &String::from_str(&env, "initial"));
+    client.set_admin_delay(&3600);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(voting_v2::WASM);
+    assert!(client.try_upgrade(&new_wasm_hash).is_err());
+    assert!(client.try_set_guardian(&Address::generate(&env)).is_err());
+    assert!(client.try_set_category(&symbol_short!("Budget")).is_err());
+    assert!(client.try_set_nota_competes(&true).is_err());
+    assert!(client.try_set_max_deadline_extension(&60).is_err());
+    assert!(client.try_set_reward_pool(&token, &10, &50).is_err());
+    assert!(client.try_set_eligibility_token(&token, &1).is_err());
+    assert!(client.try_set_eligibility_oracle(&Address::generate(&env)).is_err());
+    assert!(client.try_enable_bonded_voting(&token, &10, &false).is_err());
+
+    let action_id = client.schedule(&AdminAction::SetCategory(symbol_short!("Budget")));
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.execute_scheduled(&action_id);
+    assert_eq!(client.get_category(), Some(symbol_short!("Budget")));
+}
+
+#[test]
+fn test_scheduled_upgrade() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&3600);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(voting_v2::WASM);
+    let action_id = client.schedule(&AdminAction::Upgrade(new_wasm_hash));
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.execute_scheduled(&action_id);
+
+    assert_eq!(voting_v2::Client::new(&env, &contract_id).version(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Admin delay too long")]
+fn test_admin_delay_too_long() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Action not found")]
+fn test_cancel_scheduled_action() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_admin_delay(&3600);
+
+    let action_id = client.schedule(&AdminAction::AddParty(symbol_short!("PartyA")));
+    client.cancel(&action_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.execute_scheduled(&action_id);
+}
+
+fn setup_council(env: &Env, admin: &Address) -> (VotingContractClient<'static>, Vec<Address>) {
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(admin);
+
+    let members = vec![env, Address::generate(env), Address::generate(env), Address::generate(env)];
+    client.set_council(&Council { members: members.clone(), threshold: 2 });
+    (client, members)
+}
+
+#[test]
+fn test_council_action_applies_at_threshold() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let (client, members) = setup_council(&env, &admin);
+    let party_a = symbol_short!("PartyA");
+
+    let action_id = client.propose_action(&members.get(0).unwrap(), &AdminAction::AddParty(party_a.clone()));
+    assert_eq!(client.get_parties(), Vec::new(&env));
+    assert_eq!(client.get_council_actions().get(action_id).unwrap().approvals.len(), 1);
+
+    client.approve_action(&members.get(2).unwrap(), &action_id);
+    assert_eq!(client.get_parties(), vec![&env, party_a]);
+    assert_eq!(client.get_council_actions().len(), 0);
+
+    let voter_id = client.propose_action(&members.get(1).unwrap(), &AdminAction::AddVoter(voter1.clone()));
+    client.approve_action(&members.get(0).unwrap(), &voter_id);
+    assert_eq!(client.get_voter_status(&voter1), VoterStatus::Registered);
+}
+
+#[test]
+fn test_council_action_respects_admin_delay() {
+    let (env, admin, _, _, _) = create_test_env();
+    let (client, members) = setup_council(&env, &admin);
+    let (member1, member2) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    let delay_id = client.propose_action(&member1, &AdminAction::SetAdminDelay(60));
+    client.approve_action(&member2, &delay_id);
+    assert_eq!(client.get_admin_delay(), 60);
+
+    let party_id = client.propose_action(&member1, &AdminAction::AddParty(symbol_short!("PartyA")));
+    client.approve_action(&member2, &party_id);
+    assert_eq!(client.get_parties(), Vec::new(&env));
+
+    let (scheduled_id, _) = client.get_scheduled_actions().iter().next().unwrap();
+    env.ledger().with_mut(|li| li.timestamp = 60);
+    client.execute_scheduled(&scheduled_id);
+    assert_eq!(client.get_parties().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Council approval required")]
+fn test_admin_cannot_bypass_council() {
+    let (env, admin, _, _, _) = create_test_env();
+    let (client, _) = setup_council(&env, &admin);
+
+    client.add_party(&symbol_short!("PartyA"));
+}
+
+#[test]
+fn test_council_gates_upgrade_cancel_and_unpause() {
+    let (env, admin, _, _, _) = create_test_env();
+    let (client, members) = setup_council(&env, &admin);
+    let (member1, member2) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(voting_v2::WASM);
+    assert!(client.try_upgrade(&new_wasm_hash).is_err());
+    assert!(client.try_set_guardian(&Address::generate(&env)).is_err());
+    assert!(client.try_set_reward_pool(&Address::generate(&env), &10, &50).is_err());
+
+    let delay_id = client.propose_action(&member1, &AdminAction::SetAdminDelay(60));
+    client.approve_action(&member2, &delay_id);
+    let party_id = client.propose_action(&member1, &AdminAction::AddParty(symbol_short!("PartyA")));
+    client.approve_action(&member2, &party_id);
+    let (scheduled_id, _) = client.get_scheduled_actions().iter().next().unwrap();
+
+    assert!(client.try_cancel(&scheduled_id).is_err());
+    let cancel_id = client.propose_action(&member1, &AdminAction::Cancel(scheduled_id));
+    client.approve_action(&member2, &cancel_id);
+    assert_eq!(client.get_scheduled_actions().len(), 0);
+
+    client.pause(&admin);
+    assert!(client.try_unpause().is_err());
+    let unpause_id = client.propose_action(&member1, &AdminAction::Unpause);
+    client.approve_action(&member2, &unpause_id);
+    assert!(!client.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Action already approved")]
+fn test_council_member_cannot_approve_twice() {
+    let (env, admin, _, _, _) = create_test_env();
+    let (client, members) = setup_council(&env, &admin);
+    let member = members.get(0).unwrap();
+
+    let action_id = client.propose_action(&member, &AdminAction::AddParty(symbol_short!("PartyA")));
+    client.approve_action(&member, &action_id);
+}
+
+#[contract]
+struct Treasury;
+
+#[contractimpl]
+impl Treasury {
+    pub fn can_release(env: Env, election: Address, party: Symbol) -> bool {
+        let reader = voting_interface::VotingReaderClient::new(&env, &election);
+        reader.reader_version() == voting_interface::VOTING_READER_VERSION
+            && reader.is_finalized()
+            && reader.get_winner() == Some(party.clone())
+            && reader.get_result(&party) > 0
+    }
+}
+
+#[test]
+fn test_consumer_reads_outcome_through_interface() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let treasury = TreasuryClient::new(&env, &env.register(Treasury, ()));
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+    client.vote(&voter1, &party_a);
+
+    let reader = voting_interface::VotingReaderClient::new(&env, &contract_id);
+    assert!(reader.has_voted(&voter1));
+    assert!(!reader.has_voted(&voter2));
+    assert!(!treasury.can_release(&contract_id, &party_a));
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+
+    assert!(treasury.can_release(&contract_id, &party_a));
+    assert!(!treasury.can_release(&contract_id, &symbol_short!("PartyB")));
+}
+
+mod recording_subscriber {
+    use super::*;
+    use voting_interface::FinalizationSubscriber;
+
+    #[contract]
+    pub struct RecordingSubscriber;
+
+    #[contractimpl]
+    impl FinalizationSubscriber for RecordingSubscriber {
+        fn on_finalized(env: Env, result: FinalResult) {
+            let mut calls = Self::calls(env.clone());
+            calls.push_back(result);
+            env.storage().instance().set(&symbol_short!("calls"), &calls);
+        }
+    }
+
+    #[contractimpl]
+    impl RecordingSubscriber {
+        pub fn calls(env: Env) -> Vec<FinalResult> {
+            env.storage().instance().get(&symbol_short!("calls")).unwrap_or(Vec::new(&env))
+        }
+    }
+}
+
+mod failing_subscriber {
+    use super::*;
+    use voting_interface::FinalizationSubscriber;
+
+    #[contract]
+    pub struct FailingSubscriber;
+
+    #[contractimpl]
+    impl FinalizationSubscriber for FailingSubscriber {
+        fn on_finalized(_env: Env, _result: FinalResult) {
+            panic!("subscriber failure");
+        }
+    }
+}
+
+use failing_subscriber::FailingSubscriber;
+use recording_subscriber::{RecordingSubscriber, RecordingSubscriberClient};
+
+#[test]
+fn test_finalize_notifies_subscribers() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let failing = env.register(FailingSubscriber, ());
+    let first = RecordingSubscriberClient::new(&env, &env.register(RecordingSubscriber, ()));
+    let last = RecordingSubscriberClient::new(&env, &env.register(RecordingSubscriber, ()));
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+    client.add_subscriber(&first.address);
+    client.add_subscriber(&failing);
+    client.add_subscriber(&last.address);
+    client.vote(&voter1, &party_a);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    let result = client.finalize();
+
+    let notify_ko = (contract_id.clone(), (symbol_short!("notify_ko"),).into_val(&env), failing.into_val(&env));
+    let events = env.events().all();
+    assert!(events.contains(&notify_ko));
+    assert_eq!(events.iter().filter(|event| event.1 == notify_ko.1).count(), 1);
+
+    assert_eq!(result.winner, Some(party_a.clone()));
+    assert_eq!(result.results.get(party_a), Some(1));
+    assert_eq!(result.finalized_at, 101);
+    assert_eq!(client.get_final_result(), Some(result.clone()));
+    assert_eq!(first.calls(), vec![&env, result.clone()]);
+    assert_eq!(last.calls(), vec![&env, result]);
+    assert!(client.is_finalized());
+}
+
+#[test]
+fn test_remove_subscriber() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+    let recorder = RecordingSubscriberClient::new(&env, &env.register(RecordingSubscriber, ()));
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+    client.add_subscriber(&recorder.address);
+    client.remove_subscriber(&recorder.address);
+    assert_eq!(client.get_subscribers().len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+    assert_eq!(recorder.calls().len(), 0);
+}
+
+#[test]
+fn test_multi_seat_winners_ordered_with_tie_break() {
+    let (env, admin, _, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_seats(&3, &false);
+
+    let parties = [symbol_short!("PartyA"), symbol_short!("PartyB"), symbol_short!("PartyC"), symbol_short!("PartyD")];
+    for party in parties.iter() {
+        client.add_party(party);
+    }
+
+    for (party, votes) in [(&parties[0], 1), (&parties[1], 3), (&parties[2], 1), (&parties[3], 2)] {
+        for _ in 0..votes {
+            let voter = Address::generate(&env);
+            client.add_voter(&voter);
+            client.vote(&voter, party);
+        }
+    }
+
+    assert_eq!(client.get_winners(), vec![&env, parties[1].clone(), parties[3].clone(), parties[0].clone()]);
+}
+
+#[test]
+fn test_block_voting_and_final_winners() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_seats(&2, &true);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    let party_c = symbol_short!("PartyC");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_party(&party_c);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.delegate(&voter3, &voter1, &None, &None);
+    client.vote_block(&voter1, &vec![&env, party_c.clone(), party_b.clone()]);
+    client.vote(&voter2, &party_a);
+
+    assert_eq!(client.get_vote_count(&party_b), 2);
+    assert_eq!(client.get_vote_count(&party_c), 2);
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_voting_stats().turnout, 3);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    let result = client.finalize();
+    assert_eq!(result.winners, vec![&env, party_b, party_c]);
+}
+
+#[test]
+fn test_no_seats_when_nota_wins() {
+    let (env, admin, voter1, voter2, voter3) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_seats(&2, &false);
+    client.set_nota_competes(&true);
+    client.set_voting_deadline(&Deadline::Timestamp(100), &String::from_str(&env, "initial"));
+
+    let party_a = symbol_short!("PartyA");
+    client.add_party(&party_a);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.add_voter(&voter3);
+
+    client.vote(&voter1, &party_a);
+    client.vote(&voter2, &NOTA);
+    client.vote(&voter3, &NOTA);
+
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    let result = client.finalize();
+    assert_eq!(result.winner, Some(NOTA));
+    assert_eq!(result.winners, Vec::new(&env));
+}
+
+#[test]
+#[should_panic(expected = "Quorum not reached")]
+fn test_block_ballot_counts_once_toward_quorum() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let party_a = symbol_short!("PartyA");
+    let (client, _) = setup_proposals(&env, &admin);
+    client.set_seats(&2, &true);
+    client.set_proposal_threshold(&3, &0);
+
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+    client.vote_block(&voter1, &vec![&env, party_a.clone(), symbol_short!("PartyB")]);
+    client.vote(&voter2, &party_a);
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    client.finalize();
+    env.ledger().with_mut(|li| li.timestamp = 151);
+
+    client.execute(&party_a);
+}
+
+#[test]
+#[should_panic(expected = "Invalid number of candidates")]
+fn test_block_vote_exceeding_seats() {
+    let (env, admin, voter1, _, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_seats(&1, &true);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_voter(&voter1);
+
+    client.vote_block(&voter1, &vec![&env, party_a, party_b]);
+}
+
+#[test]
+fn test_override_block_ballot_of_delegate() {
+    let (env, admin, voter1, voter2, _) = create_test_env();
+    let contract_id = env.register(VotingContract, ());
+    let client = VotingContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_seats(&2, &true);
+    client.set_delegate_override(&true);
+
+    let party_a = symbol_short!("PartyA");
+    let party_b = symbol_short!("PartyB");
+    let party_c = symbol_short!("PartyC");
+    client.add_party(&party_a);
+    client.add_party(&party_b);
+    client.add_party(&party_c);
+    client.add_voter(&voter1);
+    client.add_voter(&voter2);
+
+    client.delegate(&voter2, &voter1, &None, &None);
+    client.vote_block(&voter1, &vec![&env, party_a.clone(), party_b.clone()]);
+    client.vote(&voter2, &party_c);
+
+    assert_eq!(client.get_vote_count(&party_a), 1);
+    assert_eq!(client.get_vote_count(&party_b), 1);
+    assert_eq!(client.get_vote_count(&party_c), 1);
+}